@@ -249,6 +249,14 @@ define! {
         ],
     };
 
+    /// The function to implement for the negation operation.
+    pub const [NEG, NEG_HASH]: Protocol = Protocol {
+        name: "neg",
+        hash: 0x9ffb490461f68150u64,
+        repr: Some("let output = -$value"),
+        doc: ["Allows the `-` operator to apply to values of this type."],
+    };
+
     /// The function to implement for the multiply operation.
     pub const [MUL, MUL_HASH]: Protocol = Protocol {
         name: "mul",
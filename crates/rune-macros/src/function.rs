@@ -26,6 +26,8 @@ pub(crate) struct FunctionAttrs {
     self_type: Option<syn::PathSegment>,
     /// Defines a fallible function which can make use of the `?` operator.
     vm_result: bool,
+    /// The function is deprecated.
+    deprecated: Option<syn::LitStr>,
 }
 
 impl FunctionAttrs {
@@ -44,6 +46,9 @@ impl FunctionAttrs {
                 out.keep = true;
             } else if ident == "vm_result" {
                 out.vm_result = true;
+            } else if ident == "deprecated" {
+                input.parse::<Token![=]>()?;
+                out.deprecated = Some(input.parse()?);
             } else if ident == "protocol" {
                 input.parse::<Token![=]>()?;
                 let protocol: syn::Path = input.parse()?;
@@ -363,6 +368,11 @@ impl Function {
 
         let attr = (!real_fn_mangled).then(|| quote!(#[allow(non_snake_case)] #[doc(hidden)]));
 
+        let deprecated = match &attrs.deprecated {
+            Some(message) => quote!(Some(#message)),
+            None => quote!(None),
+        };
+
         stream.extend(quote! {
             /// Get function metadata.
             #[automatically_derived]
@@ -372,7 +382,7 @@ impl Function {
                 Ok(rune::__private::FunctionMetaData {
                     kind: rune::__private::FunctionMetaKind::#meta_kind(#name, #real_fn_path)?#build_with,
                     name: #name_string,
-                    deprecated: None,
+                    deprecated: #deprecated,
                     docs: &#docs[..],
                     arguments: &#arguments[..],
                 })
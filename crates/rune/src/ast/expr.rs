@@ -518,6 +518,7 @@ fn base(
         K!['{'] => Expr::Block(ast::ExprBlock::parse_with_meta(
             p,
             take(attributes),
+            take(&mut label),
             take(&mut async_token),
             take(&mut const_token),
             take(&mut move_token),
@@ -19,11 +19,16 @@ fn ast_parse() {
     let expr = rt::<ast::ExprBlock>("#[retry] async { 42 }");
     assert_eq!(expr.block.statements.len(), 1);
     assert_eq!(expr.attributes.len(), 1);
+
+    let expr = rt::<ast::ExprBlock>("'label: { 42 }");
+    assert_eq!(expr.block.statements.len(), 1);
+    assert!(expr.label.is_some());
 }
 
 /// A block expression.
 ///
 /// * `<block>`.
+/// * `'label: <block>`.
 /// * `async <block>`.
 /// * `const <block>`.
 #[derive(Debug, TryClone, PartialEq, Eq, Parse, ToTokens, Spanned)]
@@ -33,6 +38,9 @@ pub struct ExprBlock {
     /// The attributes for the block.
     #[rune(iter, meta)]
     pub attributes: Vec<ast::Attribute>,
+    /// An optional label followed by a colon.
+    #[rune(iter, meta)]
+    pub label: Option<(ast::Label, T![:])>,
     /// The optional async token.
     #[rune(iter, meta)]
     pub async_token: Option<T![async]>,
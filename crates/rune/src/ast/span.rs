@@ -106,6 +106,12 @@ impl Span {
     /// let b = Span::new(20, 22);
     ///
     /// assert_eq!(a.join(b), Span::new(10, 22));
+    /// assert_eq!(b.join(a), Span::new(10, 22));
+    ///
+    /// // Overlapping, adjacent and nested spans.
+    /// assert_eq!(Span::new(10, 15).join(Span::new(12, 20)), Span::new(10, 20));
+    /// assert_eq!(Span::new(10, 15).join(Span::new(15, 20)), Span::new(10, 20));
+    /// assert_eq!(Span::new(10, 20).join(Span::new(12, 15)), Span::new(10, 20));
     /// ```
     pub fn join(self, other: Self) -> Self {
         Self {
@@ -114,6 +120,53 @@ impl Span {
         }
     }
 
+    /// Test if the given byte offset is contained in the span.
+    ///
+    /// The start of the span is inclusive and the end is exclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::ast::Span;
+    ///
+    /// let span = Span::new(10, 20);
+    ///
+    /// assert!(!span.contains(9));
+    /// assert!(span.contains(10));
+    /// assert!(span.contains(19));
+    /// assert!(!span.contains(20));
+    /// assert!(!Span::point(10).contains(10));
+    /// ```
+    pub fn contains(self, offset: usize) -> bool {
+        let Ok(offset) = ByteIndex::try_from(offset) else {
+            return false;
+        };
+
+        self.start <= offset && offset < self.end
+    }
+
+    /// Test if two spans share at least one byte.
+    ///
+    /// Spans which only touch at their boundaries do not intersect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::ast::Span;
+    ///
+    /// let span = Span::new(10, 20);
+    ///
+    /// assert!(span.intersects(Span::new(15, 25)));
+    /// assert!(span.intersects(Span::new(12, 14)));
+    /// assert!(span.intersects(Span::new(0, 11)));
+    /// assert!(!span.intersects(Span::new(20, 25)));
+    /// assert!(!span.intersects(Span::new(5, 10)));
+    /// assert!(!span.intersects(Span::point(15)));
+    /// ```
+    pub fn intersects(self, other: Self) -> bool {
+        ByteIndex::max(self.start, other.start) < ByteIndex::min(self.end, other.end)
+    }
+
     /// Narrow the span with the given amount.
     ///
     /// If the narrowing causes the span to become empty, the resulting span
@@ -134,6 +187,29 @@ impl Span {
         Self { start, end }
     }
 
+    /// Shift the span by the given number of bytes.
+    ///
+    /// Both ends of the span saturate, so shifting it past the start of the
+    /// source clamps them to `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::ast::Span;
+    ///
+    /// assert_eq!(Span::new(10, 12).with_offset(5), Span::new(15, 17));
+    /// assert_eq!(Span::new(10, 12).with_offset(-10), Span::new(0, 2));
+    /// assert_eq!(Span::new(10, 12).with_offset(-11), Span::new(0, 1));
+    /// assert_eq!(Span::new(10, 12).with_offset(-20), Span::new(0, 0));
+    /// assert_eq!(Span::new(10, 12).with_offset(0), Span::new(10, 12));
+    /// ```
+    pub fn with_offset(self, delta: isize) -> Self {
+        Self {
+            start: self.start.saturating_offset(delta),
+            end: self.end.saturating_offset(delta),
+        }
+    }
+
     /// Get the span as a range of usize.
     ///
     /// # Panics
@@ -237,6 +313,16 @@ impl ByteIndex {
     fn saturating_add(self, other: Self) -> Self {
         Self(self.0.saturating_add(other.0))
     }
+
+    fn saturating_offset(self, delta: isize) -> Self {
+        let amount = u32::try_from(delta.unsigned_abs()).unwrap_or(u32::MAX);
+
+        if delta < 0 {
+            Self(self.0.saturating_sub(amount))
+        } else {
+            Self(self.0.saturating_add(amount))
+        }
+    }
 }
 
 impl From<u32> for ByteIndex {
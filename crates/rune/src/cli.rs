@@ -603,6 +603,8 @@ struct SharedFlags {
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    ///
+    /// max-const-depth=<depth> - Limit the number of nested scopes when evaluating constants.
     #[arg(name = "option", short = 'O', number_of_values = 1)]
     compiler_options: Vec<String>,
 
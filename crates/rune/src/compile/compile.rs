@@ -243,6 +243,10 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     FunctionAst::Empty(ast, span) => hir::lowering::empty_fn(&mut cx, ast, &span)?,
                 };
 
+                if cx.has_errors() {
+                    return Ok(());
+                }
+
                 let count = hir.args.len();
 
                 let mut c = self.compiler1(location, span, &mut asm)?;
@@ -293,6 +297,11 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     item_meta.location.source_id,
                 )?;
                 let hir = hir::lowering::expr_closure_secondary(&mut cx, &closure.ast, captures)?;
+
+                if cx.has_errors() {
+                    return Ok(());
+                }
+
                 let mut c = self.compiler1(location, &closure.ast, &mut asm)?;
                 assemble::expr_closure_secondary(&mut c, &hir, &closure.ast)?;
 
@@ -326,6 +335,11 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     item_meta.location.source_id,
                 )?;
                 let hir = hir::lowering::async_block_secondary(&mut cx, &b.ast, captures)?;
+
+                if cx.has_errors() {
+                    return Ok(());
+                }
+
                 let mut c = self.compiler1(location, &b.ast, &mut asm)?;
                 assemble::async_block_secondary(&mut c, &hir)?;
 
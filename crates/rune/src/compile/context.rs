@@ -33,8 +33,7 @@ pub(crate) struct ContextMeta {
     /// The kind of the compile meta.
     pub(crate) kind: meta::Kind,
     /// Deprecation notice.
-    #[cfg(feature = "doc")]
-    pub(crate) deprecated: Option<Box<str>>,
+    pub(crate) deprecated: Option<&'static str>,
     /// Documentation associated with a context meta.
     #[cfg(feature = "doc")]
     pub(crate) docs: Docs,
@@ -112,6 +111,9 @@ pub struct Context {
     crates: HashSet<Box<str>>,
     /// Constants visible in this context
     constants: hash::Map<ConstValue>,
+    /// Deprecation notices of instance functions by name, which are only set
+    /// if every instance function with that name is deprecated.
+    instance_deprecations: HashMap<Hash, Option<&'static str>>,
 }
 
 impl Context {
@@ -304,6 +306,66 @@ impl Context {
         indexes.iter().map(|&i| &self.meta[i])
     }
 
+    /// Get the variants of the enum with the given type hash as pairs of their
+    /// index and name, ordered by index.
+    ///
+    /// The result is empty if the hash doesn't belong to an enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use rune::Context;
+    /// use rune::runtime::TypeOf;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let variants = context.enum_variants(Ordering::type_hash())?;
+    /// assert_eq!(variants, [(0, "Less"), (1, "Equal"), (2, "Greater")]);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn enum_variants(&self, enum_hash: Hash) -> alloc::Result<Vec<(usize, &str)>> {
+        let mut variants = Vec::new();
+
+        for meta in &self.meta {
+            let meta::Kind::Variant {
+                enum_hash: hash,
+                index,
+                ..
+            } = &meta.kind
+            else {
+                continue;
+            };
+
+            if *hash != enum_hash {
+                continue;
+            }
+
+            if let Some(ComponentRef::Str(name)) = meta.item.as_deref().and_then(Item::last) {
+                variants.try_push((*index, name))?;
+            }
+        }
+
+        variants.sort_by_key(|&(index, _)| index);
+        Ok(variants)
+    }
+
+    /// Lookup the deprecation notice of the item with the given hash, if it
+    /// is deprecated.
+    pub(crate) fn lookup_deprecation(&self, hash: Hash) -> Option<&'static str> {
+        let indexes = self.hash_to_meta.get(&hash)?;
+        indexes.iter().find_map(|&i| self.meta[i].deprecated)
+    }
+
+    /// Lookup the deprecation notice of instance functions with the given
+    /// name hash.
+    ///
+    /// Since instance calls are resolved at runtime, this is only set if every
+    /// instance function with that name is deprecated.
+    pub(crate) fn lookup_instance_deprecation(&self, name: Hash) -> Option<&'static str> {
+        *self.instance_deprecations.get(&name)?
+    }
+
     /// Check if unit contains the given name by prefix.
     pub(crate) fn contains_prefix(&self, item: &Item) -> alloc::Result<bool> {
         self.names.contains_prefix(item)
@@ -399,11 +461,7 @@ impl Context {
                 hash: Hash::type_hash(item),
                 item: Some(item.try_to_owned()?),
                 kind: meta::Kind::Module,
-                #[cfg(feature = "doc")]
-                deprecated: common
-                    .map(|c| c.deprecated.as_ref().try_cloned())
-                    .transpose()?
-                    .flatten(),
+                deprecated: common.and_then(|c| c.deprecated),
                 #[cfg(feature = "doc")]
                 docs: common
                     .map(|c| c.docs.try_clone())
@@ -551,8 +609,7 @@ impl Context {
                                 },
                                 constructor,
                             },
-                            #[cfg(feature = "doc")]
-                            deprecated: variant.deprecated.try_clone()?,
+                            deprecated: variant.deprecated,
                             #[cfg(feature = "doc")]
                             docs: variant.docs.try_clone()?,
                         })?;
@@ -569,8 +626,7 @@ impl Context {
             hash: ty.hash,
             item: Some(item),
             kind,
-            #[cfg(feature = "doc")]
-            deprecated: ty.common.deprecated.try_clone()?,
+            deprecated: ty.common.deprecated,
             #[cfg(feature = "doc")]
             docs: ty.common.docs.try_clone()?,
         })?;
@@ -752,8 +808,7 @@ impl Context {
                             },
                             constructor,
                         },
-                        #[cfg(feature = "doc")]
-                        deprecated: variant.deprecated.try_clone()?,
+                        deprecated: variant.deprecated,
                         #[cfg(feature = "doc")]
                         docs: variant.docs.try_clone()?,
                     })?;
@@ -769,8 +824,7 @@ impl Context {
             hash,
             item: Some(item),
             kind,
-            #[cfg(feature = "doc")]
-            deprecated: module_item.common.deprecated.try_clone()?,
+            deprecated: module_item.common.deprecated,
             #[cfg(feature = "doc")]
             docs: module_item.common.docs.try_clone()?,
         })?;
@@ -836,6 +890,18 @@ impl Context {
                         .try_collect()?,
                 };
 
+                if let meta::AssociatedKind::Instance(name) = &assoc.name.kind {
+                    let name = Hash::ident(name);
+                    let deprecated = assoc.common.deprecated;
+
+                    let deprecated = match self.instance_deprecations.get(&name) {
+                        Some(existing) => existing.filter(|_| deprecated.is_some()),
+                        None => deprecated,
+                    };
+
+                    self.instance_deprecations.try_insert(name, deprecated)?;
+                }
+
                 if let Some((hash, item)) = &item {
                     self.constants.try_insert(
                         Hash::associated_function(*hash, Protocol::INTO_TYPE_NAME),
@@ -867,8 +933,7 @@ impl Context {
             hash,
             item: item.map(|(_, item)| item),
             kind,
-            #[cfg(feature = "doc")]
-            deprecated: assoc.common.deprecated.try_clone()?,
+            deprecated: assoc.common.deprecated,
             #[cfg(feature = "doc")]
             docs: assoc.common.docs.try_clone()?,
         })?;
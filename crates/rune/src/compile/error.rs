@@ -4,7 +4,9 @@ use core::fmt;
 use std::path::PathBuf;
 
 use crate as rune;
+use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
+use crate::alloc::try_format;
 use crate::alloc::{self, Box, String, Vec};
 use crate::ast;
 use crate::ast::unescape;
@@ -20,7 +22,7 @@ use crate::runtime::unit::EncodeError;
 use crate::runtime::{AccessError, TypeInfo, TypeOf};
 #[cfg(feature = "std")]
 use crate::source;
-use crate::{Hash, SourceId};
+use crate::{Hash, Source, SourceId};
 
 /// An error raised by the compiler.
 #[derive(Debug)]
@@ -68,6 +70,62 @@ impl Error {
     pub(crate) fn into_kind(self) -> ErrorKind {
         *self.kind
     }
+
+    /// Render the error against the source it was raised for, including the
+    /// line and column of its span and an underlined snippet of the offending
+    /// line.
+    ///
+    /// This doesn't depend on the `emit` feature, so it can be used to report
+    /// internal compiler errors wherever they are encountered.
+    pub fn render(&self, source: &Source) -> alloc::Result<String> {
+        let range = self.span.range();
+        let text = source.as_str();
+
+        let (line, column) = source.pos_to_utf8_linecol(range.start);
+
+        let start = text
+            .get(..range.start)
+            .and_then(|prefix| prefix.rfind('\n'))
+            .map_or(0, |n| n + 1);
+
+        let end = text
+            .get(start..)
+            .and_then(|rest| rest.find('\n'))
+            .map_or(text.len(), |n| start + n);
+
+        let contents = text
+            .get(start..end)
+            .unwrap_or_default()
+            .trim_end_matches('\r');
+
+        let width = text
+            .get(range.start..range.end.min(end))
+            .map_or(0, |mid| mid.chars().count())
+            .max(1);
+
+        let number = try_format!("{}", line + 1);
+        let pad = number.len();
+
+        let mut out = String::new();
+        writeln!(out, "{}", self.kind)?;
+        writeln!(
+            out,
+            "{:pad$}--> {}:{}:{}",
+            "",
+            source.name(),
+            line + 1,
+            column + 1
+        )?;
+        writeln!(out, "{:pad$} |", "")?;
+        writeln!(out, "{number} | {contents}")?;
+        write!(out, "{:pad$} | {:column$}", "", "")?;
+
+        for _ in 0..width {
+            out.try_push('^')?;
+        }
+
+        Ok(out)
+    }
 }
 
 impl Spanned for Error {
@@ -108,6 +166,27 @@ impl From<ir::scopes::MissingLocal> for ErrorKind {
     }
 }
 
+impl From<ir::scopes::DepthExceeded> for ErrorKind {
+    #[inline]
+    fn from(error: ir::scopes::DepthExceeded) -> Self {
+        ErrorKind::ScopeDepthExceeded { limit: error.0 }
+    }
+}
+
+impl From<ir::scopes::DuplicateLocal> for ErrorKind {
+    #[inline]
+    fn from(error: ir::scopes::DuplicateLocal) -> Self {
+        ErrorKind::DuplicateLocal { name: error.0 }
+    }
+}
+
+impl From<ir::scopes::AssignToConst> for ErrorKind {
+    #[inline]
+    fn from(error: ir::scopes::AssignToConst) -> Self {
+        ErrorKind::AssignToConst { name: error.0 }
+    }
+}
+
 impl From<anyhow::Error> for ErrorKind {
     #[inline]
     fn from(error: anyhow::Error) -> Self {
@@ -253,6 +332,19 @@ pub(crate) enum ErrorKind {
     MissingLocal {
         name: Box<str>,
     },
+    MissingLocalWithSuggestion {
+        name: Box<str>,
+        suggestion: Box<str>,
+    },
+    DuplicateLocal {
+        name: Box<str>,
+    },
+    ScopeDepthExceeded {
+        limit: usize,
+    },
+    AssignToConst {
+        name: Box<str>,
+    },
     MissingItem {
         item: ItemBuf,
     },
@@ -312,6 +404,7 @@ pub(crate) enum ErrorKind {
         number: ast::Number,
     },
     BreakOutsideOfLoop,
+    BreakDoesNotProduceValue,
     ContinueOutsideOfLoop,
     SelectMultipleDefaults,
     ExpectedBlockSemiColon {
@@ -366,6 +459,18 @@ pub(crate) enum ErrorKind {
     MissingLoopLabel {
         label: Box<str>,
     },
+    BreakMissingLabel {
+        label: Box<str>,
+    },
+    ContinueMissingLabel {
+        label: Box<str>,
+    },
+    ContinueLabeledBlock {
+        label: Box<str>,
+    },
+    BreakValueInConditionLoop {
+        label: Box<str>,
+    },
     ExpectedLeadingPathSegment,
     UnsupportedVisibility,
     ExpectedMeta {
@@ -613,6 +718,24 @@ impl fmt::Display for ErrorKind {
             ErrorKind::MissingLocal { name } => {
                 write!(f, "No local variable `{name}`")?;
             }
+            ErrorKind::MissingLocalWithSuggestion { name, suggestion } => {
+                write!(
+                    f,
+                    "No local variable `{name}`, did you mean `{suggestion}`?"
+                )?;
+            }
+            ErrorKind::DuplicateLocal { name } => {
+                write!(
+                    f,
+                    "Local variable `{name}` is already declared in this scope"
+                )?;
+            }
+            ErrorKind::ScopeDepthExceeded { limit } => {
+                write!(f, "Reached the maximum scope depth of {limit}")?;
+            }
+            ErrorKind::AssignToConst { name } => {
+                write!(f, "Cannot modify the constant local `{name}`")?;
+            }
             ErrorKind::MissingItem { item } => {
                 write!(f, "Missing item `{item}`")?;
             }
@@ -700,6 +823,9 @@ impl fmt::Display for ErrorKind {
             ErrorKind::BreakOutsideOfLoop => {
                 write!(f, "Break outside of loop")?;
             }
+            ErrorKind::BreakDoesNotProduceValue => {
+                write!(f, "Can't break out of a `while` loop with a value")?;
+            }
             ErrorKind::ContinueOutsideOfLoop => {
                 write!(f, "Continue outside of loop")?;
             }
@@ -816,6 +942,21 @@ impl fmt::Display for ErrorKind {
             ErrorKind::MissingLoopLabel { label } => {
                 write!(f, "Missing loop label `{label}`", label = label)?;
             }
+            ErrorKind::BreakMissingLabel { label } => {
+                write!(f, "No enclosing loop with the label `{label}` to break")?;
+            }
+            ErrorKind::ContinueMissingLabel { label } => {
+                write!(f, "No enclosing loop with the label `{label}` to continue")?;
+            }
+            ErrorKind::ContinueLabeledBlock { label } => {
+                write!(f, "Can't continue the labeled block `{label}`")?;
+            }
+            ErrorKind::BreakValueInConditionLoop { label } => {
+                write!(
+                    f,
+                    "Can't break out of the `while` or `for` loop `{label}` with a value"
+                )?;
+            }
             ErrorKind::ExpectedLeadingPathSegment => {
                 write!(f, "Segment is only supported in the first position")?;
             }
@@ -1319,6 +1460,16 @@ impl std::error::Error for MissingScope {}
 pub(crate) enum PopError {
     MissingScope(usize),
     MissingParentScope(usize),
+    NoScopes,
+    LengthMismatch {
+        name: Option<Box<str>>,
+        expected: usize,
+        actual: usize,
+    },
+    OutOfOrder {
+        expected: usize,
+        actual: usize,
+    },
 }
 
 impl fmt::Display for PopError {
@@ -1327,6 +1478,27 @@ impl fmt::Display for PopError {
         match self {
             PopError::MissingScope(id) => write!(f, "Missing scope with id {id}"),
             PopError::MissingParentScope(id) => write!(f, "Missing parent scope with id {id}"),
+            PopError::NoScopes => write!(f, "Expected at least one scope to pop"),
+            PopError::LengthMismatch {
+                name: Some(name),
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Scope length mismatch in `{name}`: expected {expected} but was {actual}"
+            ),
+            PopError::LengthMismatch {
+                name: None,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Scope length mismatch: expected {expected} but was {actual}"
+            ),
+            PopError::OutOfOrder { expected, actual } => write!(
+                f,
+                "Scope popped out of order: expected generation {expected} but was {actual}"
+            ),
         }
     }
 }
@@ -1334,3 +1506,45 @@ impl fmt::Display for PopError {
 cfg_std! {
     impl std::error::Error for PopError {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, ErrorKind};
+    use crate::ast::{Span, Spanned};
+    use crate::support::Result;
+    use crate::Source;
+
+    #[test]
+    fn render() -> Result<()> {
+        let source = Source::new("test", "pub fn main() {\n    let a = bee;\n}\n")?;
+
+        let error = Error::new(
+            Span::new(28, 31),
+            ErrorKind::MissingLocal {
+                name: "bee".try_into()?,
+            },
+        );
+
+        let rendered = error.render(&source)?;
+
+        assert_eq!(
+            rendered.as_str(),
+            "No local variable `bee`\n --> test:2:13\n  |\n2 |     let a = bee;\n  |             ^^^"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn nested_span() -> Result<()> {
+        let inner = Error::new(
+            Span::new(4, 8),
+            ErrorKind::MissingLocal {
+                name: "bee".try_into()?,
+            },
+        );
+
+        let outer = Error::msg(&inner, "Internal compiler error");
+        assert_eq!(outer.span(), Span::new(4, 8));
+        Ok(())
+    }
+}
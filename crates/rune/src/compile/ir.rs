@@ -54,7 +54,7 @@ impl ast::Expr {
 
         let mut ir_interpreter = Interpreter {
             budget: Budget::new(1_000_000),
-            scopes: Scopes::new()?,
+            scopes: Scopes::with_max_depth(cx.idx.q.options.max_const_depth)?,
             module: cx.item_meta.module,
             item: cx.item_meta.item,
             q: cx.idx.q.borrow(),
@@ -247,6 +247,8 @@ pub(crate) struct IrDecl {
     pub(crate) name: hir::OwnedName,
     /// The value of the variable.
     pub(crate) value: Box<Ir>,
+    /// Whether the variable is a constant, which can't be assigned to.
+    pub(crate) constant: bool,
 }
 
 /// Set a target.
@@ -1,7 +1,7 @@
 use core::mem::{replace, take};
 
 use crate::alloc::prelude::*;
-use crate::alloc::{try_format, Box, Vec};
+use crate::alloc::{try_format, try_vec, Box, Vec};
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
 use crate::compile::{self, ErrorKind, WithSpan};
@@ -100,24 +100,74 @@ fn ir_target(expr: &hir::Expr<'_>) -> compile::Result<ir::IrTarget> {
     Err(compile::Error::msg(expr, "Not supported as a target"))
 }
 
-#[instrument]
-fn expr_assign(
+/// Compile an assignment to the given expression.
+///
+/// Constants which are assigned to are declared as constant locals in a scope
+/// surrounding the assignment, so that the interpreter rejects it.
+fn assign(
     span: Span,
     c: &mut Ctxt<'_, '_>,
-    hir: &hir::ExprAssign<'_>,
+    lhs: &hir::Expr<'_>,
+    build: impl FnOnce(ir::IrTarget) -> compile::Result<ir::IrKind>,
 ) -> compile::Result<ir::Ir> {
-    let target = ir_target(&hir.lhs)?;
+    let hir::ExprKind::Const(hash) = lhs.kind else {
+        return Ok(ir::Ir::new(span, build(ir_target(lhs)?)?));
+    };
+
+    let Some(name) = c.q.sources.source(c.source_id, lhs.span()) else {
+        return Err(compile::Error::msg(lhs, "Not supported as a target"));
+    };
+
+    let name = hir::OwnedName::Str(name.try_to_owned()?);
+
+    let Some(value) = c.q.get_const_value(hash) else {
+        return Err(compile::Error::msg(
+            lhs,
+            try_format!("Missing constant for hash {hash}"),
+        ));
+    };
+
+    let value = ir::Ir::new(lhs, ir::Value::from_const(value).with_span(lhs)?);
+
+    let decl = ir::IrDecl {
+        span: lhs.span(),
+        name: name.try_clone()?,
+        value: Box::try_new(value)?,
+        constant: true,
+    };
+
+    let target = ir::IrTarget {
+        span: lhs.span(),
+        kind: ir::IrTargetKind::Name(name),
+    };
 
     Ok(ir::Ir::new(
         span,
-        ir::IrSet {
+        ir::IrScope {
             span,
-            target,
-            value: Box::try_new(expr(&hir.rhs, c)?)?,
+            instructions: try_vec![ir::Ir::new(lhs, decl)],
+            last: Some(Box::try_new(ir::Ir::new(span, build(target)?))?),
         },
     ))
 }
 
+#[instrument]
+fn expr_assign(
+    span: Span,
+    c: &mut Ctxt<'_, '_>,
+    hir: &hir::ExprAssign<'_>,
+) -> compile::Result<ir::Ir> {
+    let value = Box::try_new(expr(&hir.rhs, c)?)?;
+
+    assign(span, c, &hir.lhs, |target| {
+        Ok(ir::IrKind::Set(ir::IrSet {
+            span,
+            target,
+            value,
+        }))
+    })
+}
+
 #[instrument]
 fn expr_call(
     span: Span,
@@ -157,17 +207,16 @@ fn expr_binary(
             _ => return Err(compile::Error::msg(hir.op, "op not supported yet")),
         };
 
-        let target = ir_target(&hir.lhs)?;
+        let value = Box::try_new(expr(&hir.rhs, c)?)?;
 
-        return Ok(ir::Ir::new(
-            span,
-            ir::IrAssign {
+        return assign(span, c, &hir.lhs, |target| {
+            Ok(ir::IrKind::Assign(ir::IrAssign {
                 span,
                 target,
-                value: Box::try_new(expr(&hir.rhs, c)?)?,
+                value,
                 op,
-            },
-        ));
+            }))
+        });
     }
 
     let lhs = expr(&hir.lhs, c)?;
@@ -283,6 +332,13 @@ fn expr_object(
 pub(crate) fn block(hir: &hir::Block<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<ir::IrScope> {
     let span = hir.span();
 
+    if hir.label.is_some() {
+        return Err(compile::Error::msg(
+            span,
+            "Labeled blocks are not supported in constant contexts",
+        ));
+    }
+
     let mut last = None::<(&hir::Expr<'_>, bool)>;
     let mut instructions = Vec::new();
 
@@ -365,6 +421,7 @@ fn local(hir: &hir::Local<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<ir::Ir>
             span,
             name: hir::Name::Str(name).into_owned()?,
             value: Box::try_new(expr(&hir.expr, c)?)?,
+            constant: false,
         },
     ))
 }
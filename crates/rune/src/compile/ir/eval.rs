@@ -154,7 +154,7 @@ fn eval_ir_branches(
     used: Used,
 ) -> Result<ir::Value, EvalOutcome> {
     for (ir_condition, branch) in &ir.branches {
-        let guard = interp.scopes.push()?;
+        let guard = interp.scopes.push().with_span(branch)?;
 
         let value = eval_ir_condition(ir_condition, interp, used)?;
 
@@ -216,7 +216,13 @@ fn eval_ir_decl(
 ) -> Result<ir::Value, EvalOutcome> {
     interp.budget.take(ir)?;
     let value = eval_ir(&ir.value, interp, used)?;
-    interp.scopes.decl(&ir.name, value).with_span(ir)?;
+
+    if ir.constant {
+        interp.scopes.decl_const(&ir.name, value).with_span(ir)?;
+    } else {
+        interp.scopes.decl_mut(&ir.name, value).with_span(ir)?;
+    }
+
     Ok(ir::Value::EmptyTuple)
 }
 
@@ -228,7 +234,7 @@ fn eval_ir_loop(
     let span = ir.span();
     interp.budget.take(span)?;
 
-    let guard = interp.scopes.push()?;
+    let guard = interp.scopes.push().with_span(span)?;
 
     let value = loop {
         if let Some(condition) = &ir.condition {
@@ -292,7 +298,7 @@ fn eval_ir_scope(
     used: Used,
 ) -> Result<ir::Value, EvalOutcome> {
     interp.budget.take(ir)?;
-    let guard = interp.scopes.push()?;
+    let guard = interp.scopes.push().with_span(ir)?;
 
     for ir in &ir.instructions {
         let _ = eval_ir(ir, interp, used)?;
@@ -177,7 +177,7 @@ impl Interpreter<'_, '_> {
             ));
         }
 
-        let guard = self.scopes.isolate()?;
+        let guard = self.scopes.isolate().with_span(span)?;
 
         for (name, value) in const_fn.ir_fn.args.iter().zip(args) {
             self.scopes.decl(name, value).with_span(span)?;
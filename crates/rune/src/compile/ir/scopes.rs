@@ -1,6 +1,9 @@
+use core::fmt;
+
 use crate::alloc::prelude::*;
-use crate::alloc::{self, try_vec, Box, HashMap, Vec};
+use crate::alloc::{self, try_vec, Box, HashMap, HashSet, Vec};
 use crate::ast::Spanned;
+use crate::compile::error::PopError;
 use crate::compile::ir;
 use crate::compile::{self, ErrorKind};
 use crate::hir;
@@ -8,16 +11,67 @@ use crate::hir;
 /// Error indicating that a local variable is missing.
 pub(crate) struct MissingLocal(pub(crate) Box<str>);
 
+/// Error indicating that a local variable is already declared in the current
+/// scope.
+pub(crate) struct DuplicateLocal(pub(crate) Box<str>);
+
+/// Error indicating that the maximum depth of scopes has been reached.
+pub(crate) struct DepthExceeded(pub(crate) usize);
+
+/// Error indicating that a constant local variable was accessed mutably.
+pub(crate) struct AssignToConst(pub(crate) Box<str>);
+
+/// Calculate the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> alloc::Result<usize> {
+    let mut row = Vec::new();
+
+    for i in 0..=b.chars().count() {
+        row.try_push(i)?;
+    }
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.chars().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    Ok(row[row.len() - 1])
+}
+
 /// A hierarchy of constant scopes.
 pub(crate) struct Scopes {
     scopes: Vec<Scope>,
+    /// The maximum number of scopes which can be pushed, if limited.
+    max_depth: Option<usize>,
+    /// The generation of the last pushed scope, used to detect guards which
+    /// are popped out of order.
+    generation: usize,
 }
 
 impl Scopes {
     /// Construct a new empty scope.
+    #[cfg(test)]
     pub(crate) fn new() -> alloc::Result<Self> {
         Ok(Self {
             scopes: try_vec![Scope::default()],
+            max_depth: None,
+            generation: 0,
+        })
+    }
+
+    /// Construct a new empty scope which errors if more than `max_depth`
+    /// scopes are pushed, including the root scope. If `max_depth` is `None`
+    /// the depth is not limited.
+    pub(crate) fn with_max_depth(max_depth: Option<usize>) -> alloc::Result<Self> {
+        Ok(Self {
+            scopes: try_vec![Scope::default()],
+            max_depth,
+            generation: 0,
         })
     }
 
@@ -29,26 +83,238 @@ impl Scopes {
             .ok_or("expected at least one scope")?;
 
         last.locals.clear();
+        last.constants.clear();
         Ok(())
     }
 
+    /// Clear the current scope, returning the number of locals which were
+    /// removed from it.
+    #[cfg(test)]
+    pub(crate) fn clear_current_count(&mut self) -> Result<usize, &'static str> {
+        let last = self
+            .scopes
+            .last_mut()
+            .ok_or("expected at least one scope")?;
+
+        let count = last.locals.len();
+        last.locals.clear();
+        last.constants.clear();
+        Ok(count)
+    }
+
+    /// Clear the current scope, returning the locals which were removed from
+    /// it.
+    ///
+    /// The locals are returned in no particular order.
+    #[cfg(test)]
+    pub(crate) fn drain_current(&mut self) -> Result<Vec<(hir::OwnedName, ir::Value)>, ErrorKind> {
+        let last = self
+            .last_mut()
+            .ok_or_else(|| ErrorKind::msg("Expected at least one scope"))?;
+
+        // NB: allocating up front ensures that the scope is only cleared once
+        // the locals can be moved out of it.
+        let mut locals = Vec::try_with_capacity(last.locals.len())?;
+
+        for local in last.locals.drain() {
+            locals.try_push(local)?;
+        }
+
+        last.constants.clear();
+        Ok(locals)
+    }
+
+    /// Get the number of scopes currently pushed.
+    ///
+    /// Scopes are constructed with a root scope, so the depth is only zero if
+    /// that scope has been popped.
+    #[cfg(test)]
+    pub(crate) fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Test if the current scope has no locals declared in it.
+    #[cfg(test)]
+    pub(crate) fn current_is_empty(&self) -> bool {
+        self.scopes
+            .last()
+            .map_or(true, |scope| scope.locals.is_empty())
+    }
+
+    /// Get the total number of locals declared across all scopes.
+    ///
+    /// Locals shadowed by a scope further in are counted separately.
+    #[cfg(test)]
+    pub(crate) fn total_locals(&self) -> usize {
+        self.scopes.iter().map(|scope| scope.locals.len()).sum()
+    }
+
     /// Declare a value in the scope.
     pub(crate) fn decl(
         &mut self,
         name: &hir::OwnedName,
         value: ir::Value,
+    ) -> Result<(), ErrorKind> {
+        self.decl_returning(name, value)?;
+        Ok(())
+    }
+
+    /// Declare a value in the scope, returning the value it replaced in the
+    /// current scope if any.
+    pub(crate) fn decl_returning(
+        &mut self,
+        name: &hir::OwnedName,
+        value: ir::Value,
+    ) -> Result<Option<ir::Value>, ErrorKind> {
+        let last = self
+            .last_mut()
+            .ok_or_else(|| ErrorKind::msg("Expected at least one scope"))?;
+        Ok(last.insert(name.try_clone()?, value)?)
+    }
+
+    /// Declare a constant value in the scope.
+    ///
+    /// Accessing the value mutably through [`Scopes::get_name_mut`] errors
+    /// until it's shadowed or redeclared as mutable.
+    pub(crate) fn decl_const(
+        &mut self,
+        name: &hir::OwnedName,
+        value: ir::Value,
     ) -> Result<(), ErrorKind> {
         let last = self
             .last_mut()
             .ok_or_else(|| ErrorKind::msg("Expected at least one scope"))?;
         last.locals.try_insert(name.try_clone()?, value)?;
+        last.constants.try_insert(name.try_clone()?)?;
+        Ok(())
+    }
+
+    /// Declare a mutable value in the scope.
+    ///
+    /// This is the same as [`Scopes::decl`], since values are mutable unless
+    /// declared with [`Scopes::decl_const`].
+    pub(crate) fn decl_mut(
+        &mut self,
+        name: &hir::OwnedName,
+        value: ir::Value,
+    ) -> Result<(), ErrorKind> {
+        self.decl(name, value)
+    }
+
+    /// Declare all the given values in the current scope.
+    ///
+    /// If the same name occurs more than once the last value wins. The
+    /// declarations are applied atomically, so if any of them fail the
+    /// current scope is left unmodified.
+    #[cfg(test)]
+    pub(crate) fn decl_all<I>(&mut self, locals: I) -> Result<(), ErrorKind>
+    where
+        I: IntoIterator<Item = (hir::OwnedName, ir::Value)>,
+    {
+        let mut staged = HashMap::new();
+
+        for (name, value) in locals {
+            staged.try_insert(name, value)?;
+        }
+
+        let last = self
+            .last_mut()
+            .ok_or_else(|| ErrorKind::msg("Expected at least one scope"))?;
+
+        // NB: reserving up front ensures that the inserts below don't
+        // allocate, so they can't fail part way through.
+        last.locals.try_reserve(staged.len())?;
+
+        for (name, value) in staged {
+            last.insert(name, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Extend the current scope with the given locals, attributing any error
+    /// to `spanned`.
+    ///
+    /// This behaves like [`Scopes::decl_all`], so the last value wins if the
+    /// same name occurs more than once.
+    #[cfg(test)]
+    pub(crate) fn extend_current<I, S>(&mut self, locals: I, spanned: S) -> compile::Result<()>
+    where
+        I: IntoIterator<Item = (hir::OwnedName, ir::Value)>,
+        S: Spanned,
+    {
+        match self.decl_all(locals) {
+            Ok(()) => Ok(()),
+            Err(kind) => Err(compile::Error::new(spanned, kind)),
+        }
+    }
+
+    /// Declare a value in the current scope unless a value with the same name
+    /// is already visible from it, attributing any error to `spanned`.
+    ///
+    /// Returns `true` if the value was declared and `false` if an existing
+    /// binding was found, in which case it is left untouched.
+    #[cfg(test)]
+    pub(crate) fn decl_if_absent<S>(
+        &mut self,
+        name: &hir::OwnedName,
+        value: ir::Value,
+        spanned: S,
+    ) -> compile::Result<bool>
+    where
+        S: Spanned,
+    {
+        if self.try_get(name).is_some() {
+            return Ok(false);
+        }
+
+        match self.decl(name, value) {
+            Ok(()) => Ok(true),
+            Err(kind) => Err(compile::Error::new(spanned, kind)),
+        }
+    }
+
+    /// Declare a value in the scope, erroring if a value with the same name
+    /// has already been declared in the current scope.
+    #[cfg(test)]
+    pub(crate) fn decl_unique(
+        &mut self,
+        name: &hir::OwnedName,
+        value: ir::Value,
+    ) -> Result<(), ErrorKind> {
+        let last = self
+            .last_mut()
+            .ok_or_else(|| ErrorKind::msg("Expected at least one scope"))?;
+
+        if last.locals.contains_key(name) {
+            return Err(ErrorKind::from(DuplicateLocal(
+                name.try_to_string()?.try_into_boxed_str()?,
+            )));
+        }
+
+        last.insert(name.try_clone()?, value)?;
         Ok(())
     }
 
     /// Try to get the value out from the scopes.
     pub(crate) fn try_get(&self, name: &hir::OwnedName) -> Option<&ir::Value> {
-        for scope in self.scopes.iter().rev() {
-            if let Some(current) = scope.locals.get(name) {
+        let (value, _) = self.try_get_with_depth(name)?;
+        Some(value)
+    }
+
+    /// Try to get the value out from the scopes mutably.
+    ///
+    /// Constants can't be accessed mutably, so this returns `None` if the
+    /// variable was declared as a constant. Use [`Scopes::get_name_mut`] to
+    /// report why a variable couldn't be accessed.
+    #[cfg(test)]
+    pub(crate) fn try_get_mut(&mut self, name: &hir::OwnedName) -> Option<&mut ir::Value> {
+        for scope in self.scopes.iter_mut().rev() {
+            if scope.constants.contains(name) {
+                return None;
+            }
+
+            if let Some(current) = scope.locals.get_mut(name) {
                 return Some(current);
             }
 
@@ -61,6 +327,111 @@ impl Scopes {
         None
     }
 
+    /// Try to get the value out from the scopes, together with the depth of
+    /// the scope it was found in.
+    ///
+    /// The depth is counted from the innermost scope, so a value declared in
+    /// the current scope has a depth of `0`.
+    pub(crate) fn try_get_with_depth(&self, name: &hir::OwnedName) -> Option<(&ir::Value, usize)> {
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if let Some(current) = scope.locals.get(name) {
+                return Some((current, depth));
+            }
+
+            // don't look past isolate scopes.
+            if let ScopeKind::Isolate = scope.kind {
+                break;
+            }
+        }
+
+        None
+    }
+
+    /// Iterate over all visible locals, from the innermost scope to the
+    /// outermost.
+    ///
+    /// Locals which are shadowed by a local in an inner scope are not
+    /// included.
+    pub(crate) fn iter_visible(&self) -> impl Iterator<Item = (&hir::OwnedName, &ir::Value)> {
+        let visible = self.visible();
+
+        visible
+            .iter()
+            .rev()
+            .enumerate()
+            .flat_map(move |(depth, scope)| {
+                let inner = &visible[visible.len() - depth..];
+
+                scope
+                    .locals
+                    .iter()
+                    .filter(move |(name, _)| !inner.iter().any(|s| s.locals.contains_key(*name)))
+            })
+    }
+
+    /// Find the first visible local for which `predicate` returns `true`,
+    /// searching from the innermost scope to the outermost.
+    ///
+    /// Only locals with a string name are considered, and locals which are
+    /// shadowed by a local in an inner scope are skipped.
+    #[cfg(test)]
+    pub(crate) fn find<P>(&self, mut predicate: P) -> Option<(&str, &ir::Value)>
+    where
+        P: FnMut(&str, &ir::Value) -> bool,
+    {
+        self.iter_visible().find_map(|(name, value)| {
+            let hir::OwnedName::Str(name) = name else {
+                return None;
+            };
+
+            predicate(name, value).then_some((name.as_str(), value))
+        })
+    }
+
+    /// Get every visible binding of the given variable, from the innermost
+    /// scope to the outermost.
+    ///
+    /// The first value is the one which shadows the others, and an empty
+    /// result means the variable isn't declared.
+    #[cfg(test)]
+    pub(crate) fn get_all(&self, name: &hir::OwnedName) -> alloc::Result<Vec<&ir::Value>> {
+        self.visible()
+            .iter()
+            .rev()
+            .filter_map(|scope| scope.locals.get(name))
+            .try_collect()
+    }
+
+    /// Visit every local in every scope, from the outermost scope (index `0`)
+    /// to the innermost.
+    ///
+    /// Unlike [`Scopes::iter_visible`], shadowed locals and locals hidden
+    /// behind an isolated scope are visited as well.
+    #[cfg(test)]
+    pub(crate) fn visit_all<F>(&self, mut f: F)
+    where
+        F: FnMut(usize, &hir::OwnedName, &ir::Value),
+    {
+        for (index, scope) in self.scopes.iter().enumerate() {
+            for (name, value) in &scope.locals {
+                f(index, name, value);
+            }
+        }
+    }
+
+    /// Iterate over the locals of the scope at the given depth.
+    ///
+    /// The depth is counted from the innermost scope, which has a depth of
+    /// `0`. Returns `None` if there is no scope at the given depth.
+    #[cfg(test)]
+    pub(crate) fn scope_locals(
+        &self,
+        depth: usize,
+    ) -> Option<impl Iterator<Item = (&hir::OwnedName, &ir::Value)>> {
+        let scope = self.scopes.iter().rev().nth(depth)?;
+        Some(scope.locals.iter())
+    }
+
     /// Get the given variable.
     pub(crate) fn get_name(
         &self,
@@ -78,10 +449,53 @@ impl Scopes {
             }
         }
 
-        Err(compile::Error::new(
-            span,
-            MissingLocal(name.try_to_string()?.try_into_boxed_str()?),
-        ))
+        let missing = name.try_to_string()?.try_into_boxed_str()?;
+
+        if let Some(suggestion) = self.suggest(name)? {
+            return Err(compile::Error::new(
+                span,
+                ErrorKind::MissingLocalWithSuggestion {
+                    name: missing,
+                    suggestion: suggestion.try_into()?,
+                },
+            ));
+        }
+
+        Err(compile::Error::new(span, MissingLocal(missing)))
+    }
+
+    /// Find the visible local with the name closest to `name`, if any is close
+    /// enough to be a plausible typo.
+    fn suggest(&self, name: &hir::OwnedName) -> alloc::Result<Option<&str>> {
+        let hir::OwnedName::Str(name) = name else {
+            return Ok(None);
+        };
+
+        let max_distance = usize::max(1, name.chars().count() / 3);
+        let mut best = None::<(usize, &str)>;
+
+        for (candidate, _) in self.iter_visible() {
+            let hir::OwnedName::Str(candidate) = candidate else {
+                continue;
+            };
+
+            let distance = edit_distance(name, candidate)?;
+
+            if distance > max_distance {
+                continue;
+            }
+
+            let better = match best {
+                Some((d, c)) => (distance, candidate.as_str()) < (d, c),
+                None => true,
+            };
+
+            if better {
+                best = Some((distance, candidate.as_str()));
+            }
+        }
+
+        Ok(best.map(|(_, candidate)| candidate))
     }
 
     /// Get the given variable as mutable.
@@ -91,6 +505,13 @@ impl Scopes {
         span: &dyn Spanned,
     ) -> compile::Result<&mut ir::Value> {
         for scope in self.scopes.iter_mut().rev() {
+            if scope.constants.contains(name) {
+                return Err(compile::Error::new(
+                    span,
+                    AssignToConst(name.try_to_string()?.try_into_boxed_str()?),
+                ));
+            }
+
             if let Some(current) = scope.locals.get_mut(name) {
                 return Ok(current);
             }
@@ -107,64 +528,1278 @@ impl Scopes {
         ))
     }
 
-    /// Push a scope and return the guard associated with the scope.
-    pub(crate) fn push(&mut self) -> alloc::Result<ScopeGuard> {
-        let length = self.scopes.len();
-        self.scopes.try_push(Scope::default())?;
-        Ok(ScopeGuard { length })
-    }
+    /// Get the given variable as mutable, or declare it in the current scope
+    /// using the value produced by `f` if it isn't visible from it.
+    #[cfg(test)]
+    pub(crate) fn get_or_decl_with<F>(
+        &mut self,
+        name: &hir::OwnedName,
+        f: F,
+    ) -> Result<&mut ir::Value, ErrorKind>
+    where
+        F: FnOnce() -> ir::Value,
+    {
+        let mut index = None;
 
-    /// Push an isolate scope and return the guard associated with the scope.
-    pub(crate) fn isolate(&mut self) -> alloc::Result<ScopeGuard> {
-        let length = self.scopes.len();
-        let scope = Scope {
-            kind: ScopeKind::Isolate,
-            ..Default::default()
+        for (n, scope) in self.scopes.iter().enumerate().rev() {
+            if scope.locals.contains_key(name) {
+                index = Some(n);
+                break;
+            }
+
+            // don't look past isolate scopes.
+            if let ScopeKind::Isolate = scope.kind {
+                break;
+            }
+        }
+
+        let scope = match index {
+            Some(index) => {
+                let scope = &mut self.scopes[index];
+
+                if scope.constants.contains(name) {
+                    return Err(ErrorKind::from(AssignToConst(
+                        name.try_to_string()?.try_into_boxed_str()?,
+                    )));
+                }
+
+                scope
+            }
+            None => {
+                let last = self
+                    .last_mut()
+                    .ok_or_else(|| ErrorKind::msg("Expected at least one scope"))?;
+                last.insert(name.try_clone()?, f())?;
+                last
+            }
         };
-        self.scopes.try_push(scope)?;
-        Ok(ScopeGuard { length })
+
+        scope
+            .locals
+            .get_mut(name)
+            .ok_or_else(|| ErrorKind::msg("Expected local to be declared"))
     }
 
-    pub(crate) fn pop(&mut self, guard: ScopeGuard) -> Result<(), &'static str> {
-        if self.scopes.pop().is_none() {
-            return Err("expected at least one scope to pop");
-        }
+    /// Replace the value of the given variable in the innermost scope it's
+    /// visible in, returning the value it previously had.
+    ///
+    /// Unlike [`Scopes::decl`] this never introduces a new binding, instead
+    /// erroring if the variable is missing.
+    #[cfg(test)]
+    pub(crate) fn replace<S>(
+        &mut self,
+        name: &hir::OwnedName,
+        value: ir::Value,
+        spanned: S,
+    ) -> compile::Result<ir::Value>
+    where
+        S: Spanned,
+    {
+        let current = self.get_name_mut(name, &spanned)?;
+        Ok(core::mem::replace(current, value))
+    }
 
-        if self.scopes.len() != guard.length {
-            return Err("scope length mismatch");
+    /// Remove the given variable from the innermost scope it's declared in and
+    /// return its value.
+    ///
+    /// Variables with the same name in outer scopes are left untouched.
+    #[cfg(test)]
+    pub(crate) fn remove(
+        &mut self,
+        name: &hir::OwnedName,
+        span: &dyn Spanned,
+    ) -> compile::Result<ir::Value> {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(value) = scope.remove(name) {
+                return Ok(value);
+            }
+
+            // don't look past isolate scopes.
+            if let ScopeKind::Isolate = scope.kind {
+                break;
+            }
         }
 
-        Ok(())
+        Err(compile::Error::new(
+            span,
+            MissingLocal(name.try_to_string()?.try_into_boxed_str()?),
+        ))
     }
 
-    /// Get the last scope mutably.
-    pub(crate) fn last_mut(&mut self) -> Option<&mut Scope> {
-        self.scopes.last_mut()
-    }
-}
+    /// Rename the innermost visible variable named `from` to `to`, keeping it
+    /// in the scope it was declared in.
+    ///
+    /// Errors if `from` isn't declared, or if `to` is already declared in the
+    /// same scope.
+    #[cfg(test)]
+    pub(crate) fn rename(
+        &mut self,
+        from: &hir::OwnedName,
+        to: &hir::OwnedName,
+        span: &dyn Spanned,
+    ) -> compile::Result<()> {
+        for scope in self.scopes.iter_mut().rev() {
+            if scope.locals.contains_key(from) {
+                if from == to {
+                    return Ok(());
+                }
 
-#[repr(transparent)]
-pub(crate) struct ScopeGuard {
-    length: usize,
-}
+                if scope.locals.contains_key(to) {
+                    return Err(compile::Error::new(
+                        span,
+                        DuplicateLocal(to.try_to_string()?.try_into_boxed_str()?),
+                    ));
+                }
 
-#[derive(Debug, Clone, Copy)]
-enum ScopeKind {
-    None,
-    Isolate,
-}
+                let constant = scope.constants.contains(from);
 
-pub(crate) struct Scope {
-    kind: ScopeKind,
-    /// Locals in the current scope.
-    locals: HashMap<hir::OwnedName, ir::Value>,
-}
+                if let Some(value) = scope.remove(from) {
+                    scope.insert(to.try_clone()?, value)?;
+                }
 
-impl Default for Scope {
-    fn default() -> Self {
-        Self {
-            kind: ScopeKind::None,
-            locals: HashMap::new(),
+                if constant {
+                    scope.constants.try_insert(to.try_clone()?)?;
+                }
+
+                return Ok(());
+            }
+
+            // don't look past isolate scopes.
+            if let ScopeKind::Isolate = scope.kind {
+                break;
+            }
+        }
+
+        Err(compile::Error::new(
+            span,
+            MissingLocal(from.try_to_string()?.try_into_boxed_str()?),
+        ))
+    }
+
+    /// Copy the value of the innermost visible variable named `from` into a
+    /// variable named `to`, declared in the same scope as `from`.
+    ///
+    /// The copy is shallow, so compound values are shared between the two
+    /// variables. Any existing variable named `to` in that scope is replaced.
+    #[cfg(test)]
+    pub(crate) fn clone_local(
+        &mut self,
+        from: &hir::OwnedName,
+        to: &hir::OwnedName,
+        span: &dyn Spanned,
+    ) -> compile::Result<()> {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(value) = scope.locals.get(from) {
+                let value = value.try_clone()?;
+                scope.insert(to.try_clone()?, value)?;
+                return Ok(());
+            }
+
+            // don't look past isolate scopes.
+            if let ScopeKind::Isolate = scope.kind {
+                break;
+            }
         }
+
+        Err(compile::Error::new(
+            span,
+            MissingLocal(from.try_to_string()?.try_into_boxed_str()?),
+        ))
+    }
+
+    /// Push a scope and return the guard associated with the scope.
+    pub(crate) fn push(&mut self) -> Result<ScopeGuard, ErrorKind> {
+        self.push_scope(Scope::default())
+    }
+
+    /// Push a named scope and return the guard associated with the scope.
+    ///
+    /// The name is used to attribute errors when the scope is popped.
+    #[cfg(test)]
+    pub(crate) fn push_named(&mut self, name: &str) -> Result<ScopeGuard, ErrorKind> {
+        self.push_scope(Scope {
+            name: Some(name.try_into()?),
+            ..Default::default()
+        })
+    }
+
+    /// Push a scope populated with the given locals and return the guard
+    /// associated with the scope.
+    #[cfg(test)]
+    pub(crate) fn push_with<I>(&mut self, locals: I) -> Result<ScopeGuard, ErrorKind>
+    where
+        I: IntoIterator<Item = (hir::OwnedName, ir::Value)>,
+    {
+        let mut scope = Scope::default();
+
+        for (name, value) in locals {
+            scope.insert(name, value)?;
+        }
+
+        self.push_scope(scope)
+    }
+
+    /// Push an isolate scope and return the guard associated with the scope.
+    pub(crate) fn isolate(&mut self) -> Result<ScopeGuard, ErrorKind> {
+        self.push_scope(Scope {
+            kind: ScopeKind::Isolate,
+            ..Default::default()
+        })
+    }
+
+    /// Push the given scope, unless doing so would exceed the maximum depth.
+    fn push_scope(&mut self, mut scope: Scope) -> Result<ScopeGuard, ErrorKind> {
+        let length = self.scopes.len();
+
+        if let Some(max_depth) = self.max_depth {
+            if length >= max_depth {
+                return Err(ErrorKind::from(DepthExceeded(max_depth)));
+            }
+        }
+
+        let generation = self.generation.wrapping_add(1);
+        scope.generation = generation;
+        self.scopes.try_push(scope)?;
+        self.generation = generation;
+        Ok(ScopeGuard { length, generation })
+    }
+
+    pub(crate) fn pop(&mut self, guard: ScopeGuard) -> Result<(), PopError> {
+        let Some(scope) = self.scopes.pop() else {
+            return Err(PopError::NoScopes);
+        };
+
+        if self.scopes.len() != guard.length {
+            return Err(PopError::LengthMismatch {
+                name: scope.name,
+                expected: guard.length,
+                actual: self.scopes.len(),
+            });
+        }
+
+        if scope.generation != guard.generation {
+            return Err(PopError::OutOfOrder {
+                expected: guard.generation,
+                actual: scope.generation,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Pop the current scope, moving all of its locals into the parent scope.
+    ///
+    /// Locals in the parent scope with the same name are overwritten.
+    #[cfg(test)]
+    pub(crate) fn pop_merging(&mut self, guard: ScopeGuard) -> Result<(), ErrorKind> {
+        let Some(scope) = self.scopes.pop() else {
+            return Err(ErrorKind::from(PopError::NoScopes));
+        };
+
+        if self.scopes.len() != guard.length {
+            return Err(ErrorKind::from(PopError::LengthMismatch {
+                name: scope.name,
+                expected: guard.length,
+                actual: self.scopes.len(),
+            }));
+        }
+
+        if scope.generation != guard.generation {
+            return Err(ErrorKind::from(PopError::OutOfOrder {
+                expected: guard.generation,
+                actual: scope.generation,
+            }));
+        }
+
+        let Some(parent) = self.scopes.last_mut() else {
+            return Err(ErrorKind::from(PopError::MissingParentScope(guard.length)));
+        };
+
+        parent.locals.try_reserve(scope.locals.len())?;
+
+        for (name, value) in scope.locals {
+            parent.insert(name, value)?;
+        }
+
+        for name in scope.constants {
+            parent.constants.try_insert(name)?;
+        }
+
+        Ok(())
+    }
+
+    /// Pop the current scope if there is one, without checking it against a
+    /// [`ScopeGuard`].
+    ///
+    /// Returns `None` if all scopes have already been popped.
+    #[cfg(test)]
+    pub(crate) fn try_pop(&mut self) -> Option<Scope> {
+        self.scopes.pop()
+    }
+
+    /// Push a scope, run the given closure and pop the scope again.
+    ///
+    /// The scope is popped regardless of whether the closure succeeds or not,
+    /// so the scopes are kept balanced even if it errors.
+    #[cfg(test)]
+    pub(crate) fn with_scope<S, R, E, F>(&mut self, spanned: S, f: F) -> Result<R, E>
+    where
+        S: Spanned,
+        E: From<compile::Error>,
+        F: FnOnce(&mut Self) -> Result<R, E>,
+    {
+        let guard = self
+            .push()
+            .map_err(|error| compile::Error::new(&spanned, error))?;
+        let output = f(self);
+        self.pop(guard)
+            .map_err(|error| compile::Error::new(&spanned, error))?;
+        output
+    }
+
+    /// Get the scopes which are visible from the current scope, from the
+    /// outermost to the innermost.
+    fn visible(&self) -> &[Scope] {
+        let start = self
+            .scopes
+            .iter()
+            .rposition(|scope| matches!(scope.kind, ScopeKind::Isolate))
+            .unwrap_or_default();
+
+        &self.scopes[start..]
+    }
+
+    /// Get the last scope mutably.
+    pub(crate) fn last_mut(&mut self) -> Option<&mut Scope> {
+        self.scopes.last_mut()
+    }
+}
+
+impl fmt::Debug for Scopes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scopes")
+            .field("scopes", &self.scopes)
+            .field("max_depth", &self.max_depth)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+pub(crate) struct ScopeGuard {
+    length: usize,
+    generation: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ScopeKind {
+    None,
+    Isolate,
+}
+
+pub(crate) struct Scope {
+    kind: ScopeKind,
+    /// The name of the scope, used for error attribution.
+    name: Option<Box<str>>,
+    /// The generation the scope was pushed with.
+    generation: usize,
+    /// Locals in the current scope.
+    locals: HashMap<hir::OwnedName, ir::Value>,
+    /// Locals in the current scope which are constant.
+    constants: HashSet<hir::OwnedName>,
+}
+
+impl Scope {
+    /// Insert a mutable local, replacing any existing local with the same
+    /// name.
+    fn insert(
+        &mut self,
+        name: hir::OwnedName,
+        value: ir::Value,
+    ) -> alloc::Result<Option<ir::Value>> {
+        self.constants.remove(&name);
+        self.locals.try_insert(name, value)
+    }
+
+    /// Remove a local, regardless of whether it's constant or not.
+    #[cfg(test)]
+    fn remove(&mut self, name: &hir::OwnedName) -> Option<ir::Value> {
+        self.constants.remove(name);
+        self.locals.remove(name)
+    }
+}
+
+impl fmt::Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("kind", &self.kind)
+            .field("name", &self.name)
+            .field("generation", &self.generation)
+            .field("locals", &self.locals)
+            .field("constants", &self.constants)
+            .finish()
+    }
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self {
+            kind: ScopeKind::None,
+            name: None,
+            generation: 0,
+            locals: HashMap::new(),
+            constants: HashSet::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scopes;
+    use crate::alloc::prelude::*;
+    use crate::alloc::{try_vec, Vec};
+    use crate::ast::{Span, Spanned};
+    use crate::compile::{self, ir, ErrorKind, WithSpan};
+    use crate::hir;
+    use crate::support::Result;
+
+    fn name(name: &str) -> Result<hir::OwnedName> {
+        Ok(hir::OwnedName::Str(name.try_to_owned()?))
+    }
+
+    fn integer(value: &ir::Value) -> Option<i64> {
+        match value {
+            ir::Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn get_with_depth() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(3))?;
+
+        assert!(matches!(
+            scopes.try_get_with_depth(&name("a")?),
+            Some((ir::Value::Integer(3), 0))
+        ));
+
+        assert!(matches!(
+            scopes.try_get_with_depth(&name("b")?),
+            Some((ir::Value::Integer(2), 1))
+        ));
+
+        assert!(scopes.try_get_with_depth(&name("c")?).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn depth() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        assert_eq!(scopes.depth(), 1);
+
+        let outer = scopes.push()?;
+        assert_eq!(scopes.depth(), 2);
+
+        let inner = scopes.isolate()?;
+        assert_eq!(scopes.depth(), 3);
+
+        scopes.pop(inner)?;
+        assert_eq!(scopes.depth(), 2);
+
+        scopes.pop(outer)?;
+        assert_eq!(scopes.depth(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn current_is_empty() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        assert!(scopes.current_is_empty());
+
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        assert!(!scopes.current_is_empty());
+
+        let guard = scopes.push()?;
+        assert!(scopes.current_is_empty());
+        scopes.pop(guard)?;
+
+        scopes.clear_current().map_err(anyhow::Error::msg)?;
+        assert!(scopes.current_is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn decl_overwrites() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("a")?, ir::Value::Integer(2))?;
+        assert!(matches!(
+            scopes.try_get(&name("a")?),
+            Some(ir::Value::Integer(2))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn decl_returning() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        let x = name("x")?;
+
+        assert!(scopes.decl_returning(&x, ir::Value::Integer(1))?.is_none());
+
+        assert!(matches!(
+            scopes.decl_returning(&x, ir::Value::Integer(2))?,
+            Some(ir::Value::Integer(1))
+        ));
+
+        // Declaring in a child scope doesn't replace the outer value.
+        let _guard = scopes.push()?;
+        assert!(scopes.decl_returning(&x, ir::Value::Integer(3))?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn get_or_decl_with() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _guard = scopes.push()?;
+
+        let value = scopes.get_or_decl_with(&name("a")?, || ir::Value::Integer(10))?;
+        assert!(matches!(value, ir::Value::Integer(1)));
+        *value = ir::Value::Integer(2);
+
+        assert!(matches!(
+            scopes.try_get_with_depth(&name("a")?),
+            Some((ir::Value::Integer(2), 1))
+        ));
+
+        let value = scopes.get_or_decl_with(&name("b")?, || ir::Value::Integer(3))?;
+        assert!(matches!(value, ir::Value::Integer(3)));
+
+        assert!(matches!(
+            scopes.try_get_with_depth(&name("b")?),
+            Some((ir::Value::Integer(3), 0))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn decl_unique() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl_unique(&name("a")?, ir::Value::Integer(1))?;
+
+        let error = scopes
+            .decl_unique(&name("a")?, ir::Value::Integer(2))
+            .unwrap_err();
+        assert!(matches!(error, ErrorKind::DuplicateLocal { name } if &*name == "a"));
+        assert!(matches!(
+            scopes.try_get(&name("a")?),
+            Some(ir::Value::Integer(1))
+        ));
+
+        // Declaring in a child scope is fine.
+        let _guard = scopes.push()?;
+        scopes.decl_unique(&name("a")?, ir::Value::Integer(3))?;
+        Ok(())
+    }
+
+    #[test]
+    fn iter_visible() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        assert_eq!(scopes.iter_visible().count(), 0);
+
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(3))?;
+
+        let mut visible = Vec::new();
+
+        for (name, value) in scopes.iter_visible() {
+            visible.try_push((name.try_to_string()?, integer(value)))?;
+        }
+
+        visible.sort();
+        assert_eq!(
+            visible,
+            [("a".try_into()?, Some(3)), ("b".try_into()?, Some(2))]
+        );
+
+        let _guard = scopes.isolate()?;
+        assert_eq!(scopes.iter_visible().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn with_scope() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        let count = scopes.scopes.len();
+        let a = name("a")?;
+
+        let value = scopes.with_scope(Span::empty(), |scopes| {
+            scopes
+                .decl(&a, ir::Value::Integer(1))
+                .with_span(Span::empty())?;
+            Ok::<_, compile::Error>(scopes.scopes.len())
+        })?;
+
+        assert_eq!(value, count + 1);
+        assert_eq!(scopes.scopes.len(), count);
+        assert!(scopes.try_get(&a).is_none());
+
+        let result = scopes.with_scope(Span::empty(), |scopes| {
+            scopes
+                .decl(&a, ir::Value::Integer(1))
+                .with_span(Span::empty())?;
+            Err::<(), _>(compile::Error::msg(Span::empty(), "failed"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(scopes.scopes.len(), count);
+        assert!(scopes.try_get(&a).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn push_with() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let guard = scopes.push_with([
+            (name("a")?, ir::Value::Integer(2)),
+            (name("b")?, ir::Value::Integer(3)),
+        ])?;
+
+        assert!(matches!(
+            scopes.try_get_with_depth(&name("a")?),
+            Some((ir::Value::Integer(2), 0))
+        ));
+        assert!(matches!(
+            scopes.try_get_with_depth(&name("b")?),
+            Some((ir::Value::Integer(3), 0))
+        ));
+
+        scopes.pop(guard)?;
+        assert!(matches!(
+            scopes.try_get(&name("a")?),
+            Some(ir::Value::Integer(1))
+        ));
+        assert!(scopes.try_get(&name("b")?).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn pop_mismatch() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        let outer = scopes.push()?;
+        let _inner = scopes.push()?;
+
+        let error = scopes.pop(outer).unwrap_err();
+        assert_eq!(
+            error.try_to_string()?.as_str(),
+            "Scope length mismatch: expected 1 but was 2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pop_named_mismatch() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        let outer = scopes.push()?;
+        let _inner = scopes.push_named("for loop")?;
+
+        let error = scopes.pop(outer).unwrap_err();
+        assert_eq!(
+            error.try_to_string()?.as_str(),
+            "Scope length mismatch in `for loop`: expected 1 but was 2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn remove() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(2))?;
+
+        let value = scopes.remove(&name("a")?, &Span::empty())?;
+        assert!(matches!(value, ir::Value::Integer(2)));
+        assert!(matches!(
+            scopes.try_get_with_depth(&name("a")?),
+            Some((ir::Value::Integer(1), 1))
+        ));
+
+        let value = scopes.remove(&name("a")?, &Span::empty())?;
+        assert!(matches!(value, ir::Value::Integer(1)));
+
+        let error = scopes.remove(&name("a")?, &Span::empty()).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::MissingLocal { name } if &**name == "a"));
+        Ok(())
+    }
+
+    #[test]
+    fn max_depth() -> Result<()> {
+        let mut scopes = Scopes::with_max_depth(Some(3))?;
+        let _outer = scopes.push()?;
+        let _inner = scopes.isolate()?;
+
+        assert!(matches!(
+            scopes.push(),
+            Err(ErrorKind::ScopeDepthExceeded { limit: 3 })
+        ));
+        assert_eq!(scopes.depth(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn clear_current_count() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("outer")?, ir::Value::Integer(0))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+        scopes.decl(&name("c")?, ir::Value::Integer(3))?;
+
+        assert_eq!(scopes.clear_current_count().map_err(anyhow::Error::msg)?, 3);
+        assert!(scopes.current_is_empty());
+        assert!(scopes.try_get(&name("outer")?).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_local_span() -> Result<()> {
+        let scopes = Scopes::new()?;
+        let span = Span::new(4, 7);
+
+        let Err(error) = scopes.get_name(&name("foo")?, &span) else {
+            panic!("expected missing local");
+        };
+
+        assert_eq!(error.span(), span);
+        assert!(matches!(error.kind(), ErrorKind::MissingLocal { name } if &**name == "foo"));
+        Ok(())
+    }
+
+    #[test]
+    fn rename() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let _guard = scopes.push()?;
+        scopes.rename(&name("a")?, &name("c")?, &Span::empty())?;
+
+        assert!(scopes.try_get(&name("a")?).is_none());
+        assert_eq!(
+            scopes
+                .try_get_with_depth(&name("c")?)
+                .map(|(value, depth)| (integer(value), depth)),
+            Some((Some(1), 1))
+        );
+
+        let Err(error) = scopes.rename(&name("d")?, &name("e")?, &Span::empty()) else {
+            panic!("expected missing local");
+        };
+
+        assert!(matches!(error.kind(), ErrorKind::MissingLocal { name } if &**name == "d"));
+
+        let Err(error) = scopes.rename(&name("b")?, &name("c")?, &Span::empty()) else {
+            panic!("expected duplicate local");
+        };
+
+        assert!(matches!(error.kind(), ErrorKind::DuplicateLocal { name } if &**name == "c"));
+        assert_eq!(scopes.try_get(&name("b")?).and_then(integer), Some(2));
+        Ok(())
+    }
+
+    #[test]
+    fn decl_all() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(0))?;
+
+        scopes.decl_all([
+            (name("a")?, ir::Value::Integer(1)),
+            (name("b")?, ir::Value::Integer(2)),
+            (name("b")?, ir::Value::Integer(3)),
+        ])?;
+
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(1));
+        assert_eq!(scopes.try_get(&name("b")?).and_then(integer), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn pop_merging() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let guard = scopes.push()?;
+        scopes.decl(&name("b")?, ir::Value::Integer(3))?;
+        scopes.decl(&name("c")?, ir::Value::Integer(4))?;
+        scopes.pop_merging(guard)?;
+
+        assert_eq!(scopes.depth(), 1);
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(1));
+        assert_eq!(scopes.try_get(&name("b")?).and_then(integer), Some(3));
+        assert_eq!(scopes.try_get(&name("c")?).and_then(integer), Some(4));
+        Ok(())
+    }
+
+    #[test]
+    fn visit_all() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(3))?;
+
+        let mut visited = Vec::new();
+        let mut error = None;
+
+        scopes.visit_all(|index, name, value| {
+            let result = name
+                .try_to_string()
+                .and_then(|name| visited.try_push((index, name, integer(value))));
+
+            if let Err(e) = result {
+                error = Some(e);
+            }
+        });
+
+        if let Some(error) = error {
+            return Err(error.into());
+        }
+
+        visited.sort();
+        assert_eq!(
+            visited,
+            [
+                (0, "a".try_into()?, Some(1)),
+                (0, "b".try_into()?, Some(2)),
+                (1, "a".try_into()?, Some(3)),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn try_get_mut() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _guard = scopes.push()?;
+        assert!(scopes.try_get_mut(&name("b")?).is_none());
+
+        if let Some(value) = scopes.try_get_mut(&name("a")?) {
+            *value = ir::Value::Integer(2);
+        }
+
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(2));
+
+        let _guard = scopes.isolate()?;
+        assert!(scopes.try_get_mut(&name("a")?).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn pop_out_of_order() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        let first = scopes.push()?;
+        assert!(scopes.try_pop().is_some());
+        let _second = scopes.push()?;
+
+        // Both guards were created at the same depth, so only the generation
+        // tells them apart.
+        let error = scopes.pop(first).unwrap_err();
+        assert_eq!(
+            error.try_to_string()?.as_str(),
+            "Scope popped out of order: expected generation 1 but was 2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn clone_local() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _guard = scopes.push()?;
+        scopes.clone_local(&name("a")?, &name("b")?, &Span::empty())?;
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(1));
+        assert_eq!(scopes.try_get(&name("b")?).and_then(integer), Some(1));
+
+        // The copy is declared next to the original.
+        assert!(scopes.current_is_empty());
+
+        let error = scopes
+            .clone_local(&name("c")?, &name("d")?, &Span::empty())
+            .unwrap_err();
+        assert_eq!(error.try_to_string()?.as_str(), "No local variable `c`");
+        Ok(())
+    }
+
+    #[test]
+    fn total_locals() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        assert_eq!(scopes.total_locals(), 0);
+
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let guard = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(3))?;
+        assert_eq!(scopes.total_locals(), 3);
+
+        scopes.pop(guard)?;
+        assert_eq!(scopes.total_locals(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn scope_locals() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _outer = scopes.push()?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let _inner = scopes.push()?;
+        scopes.decl(&name("c")?, ir::Value::Integer(3))?;
+        scopes.decl(&name("d")?, ir::Value::Integer(4))?;
+
+        let locals = |depth| -> Result<Option<Vec<_>>> {
+            let Some(iter) = scopes.scope_locals(depth) else {
+                return Ok(None);
+            };
+
+            let mut locals = Vec::new();
+
+            for (name, value) in iter {
+                locals.try_push((name.try_to_string()?, integer(value)))?;
+            }
+
+            locals.sort();
+            Ok(Some(locals))
+        };
+
+        assert_eq!(
+            locals(0)?,
+            Some(try_vec![
+                ("c".try_into()?, Some(3)),
+                ("d".try_into()?, Some(4))
+            ])
+        );
+        assert_eq!(locals(1)?, Some(try_vec![("b".try_into()?, Some(2))]));
+        assert_eq!(locals(2)?, Some(try_vec![("a".try_into()?, Some(1))]));
+        assert_eq!(locals(3)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn try_pop() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+
+        let scope = scopes.try_pop().expect("pushed scope");
+        assert!(scope.locals.contains_key(&name("b")?));
+
+        let scope = scopes.try_pop().expect("root scope");
+        assert!(scope.locals.contains_key(&name("a")?));
+
+        assert!(scopes.try_pop().is_none());
+        assert_eq!(scopes.depth(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn get_name_suggestion() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("length")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("width")?, ir::Value::Integer(2))?;
+
+        let error = scopes
+            .get_name(&name("lenght")?, &Span::empty())
+            .unwrap_err();
+
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::MissingLocalWithSuggestion { name, suggestion }
+                if &**name == "lenght" && &**suggestion == "length"
+        ));
+
+        assert_eq!(
+            error.try_to_string()?.as_str(),
+            "No local variable `lenght`, did you mean `length`?"
+        );
+
+        let error = scopes
+            .get_name(&name("height")?, &Span::empty())
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::MissingLocal { name } if &**name == "height"));
+        Ok(())
+    }
+
+    #[test]
+    fn debug() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("outer")?, ir::Value::Integer(1))?;
+
+        let _guard = scopes.push_named("block")?;
+        scopes.decl(&name("inner")?, ir::Value::Integer(2))?;
+
+        let debug = format!("{scopes:#?}");
+        let outer = debug.find("outer").expect("outer local");
+        let inner = debug.find("inner").expect("inner local");
+        assert!(outer < inner);
+        assert!(debug.contains("\"block\""));
+        Ok(())
+    }
+
+    #[test]
+    fn extend_current() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        let _guard = scopes.push()?;
+
+        scopes.extend_current(
+            [
+                (name("a")?, ir::Value::Integer(1)),
+                (name("b")?, ir::Value::Integer(2)),
+                (name("a")?, ir::Value::Integer(3)),
+            ],
+            Span::empty(),
+        )?;
+
+        assert_eq!(scopes.depth(), 2);
+        assert_eq!(
+            scopes
+                .try_get_with_depth(&name("a")?)
+                .map(|(v, d)| (integer(v), d)),
+            Some((Some(3), 0))
+        );
+        assert_eq!(scopes.try_get(&name("b")?).and_then(integer), Some(2));
+
+        scopes.try_pop();
+        scopes.try_pop();
+
+        let error = scopes
+            .extend_current([(name("c")?, ir::Value::Integer(4))], Span::new(1, 2))
+            .unwrap_err();
+        assert_eq!(error.span(), Span::new(1, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn decl_if_absent() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+
+        assert!(scopes.decl_if_absent(&name("a")?, ir::Value::Integer(1), Span::empty())?);
+        assert!(!scopes.decl_if_absent(&name("a")?, ir::Value::Integer(2), Span::empty())?);
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(1));
+
+        let _guard = scopes.push()?;
+
+        // A binding in an outer scope is visible, so nothing is shadowed.
+        assert!(!scopes.decl_if_absent(&name("a")?, ir::Value::Integer(3), Span::empty())?);
+        assert!(scopes.decl_if_absent(&name("b")?, ir::Value::Integer(4), Span::empty())?);
+
+        assert_eq!(scopes.scope_locals(0).map(Iterator::count), Some(1));
+        assert_eq!(
+            scopes
+                .try_get_with_depth(&name("a")?)
+                .map(|(v, d)| (integer(v), d)),
+            Some((Some(1), 1))
+        );
+        assert_eq!(scopes.total_locals(), 2);
+
+        scopes.try_pop();
+        scopes.try_pop();
+
+        let error = scopes
+            .decl_if_absent(&name("c")?, ir::Value::Integer(5), Span::new(1, 2))
+            .unwrap_err();
+        assert_eq!(error.span(), Span::new(1, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn replace() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _guard = scopes.push()?;
+
+        let old = scopes.replace(&name("a")?, ir::Value::Integer(2), Span::empty())?;
+        assert_eq!(integer(&old), Some(1));
+
+        // The outer binding is updated in place rather than shadowed.
+        assert_eq!(scopes.scope_locals(0).map(Iterator::count), Some(0));
+        assert_eq!(
+            scopes
+                .try_get_with_depth(&name("a")?)
+                .map(|(v, d)| (integer(v), d)),
+            Some((Some(2), 1))
+        );
+
+        let error = scopes
+            .replace(&name("b")?, ir::Value::Integer(3), Span::new(1, 2))
+            .unwrap_err();
+        assert_eq!(error.span(), Span::new(1, 2));
+        assert!(matches!(error.kind(), ErrorKind::MissingLocal { name } if &**name == "b"));
+        assert_eq!(scopes.total_locals(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn decl_const() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl_const(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl_mut(&name("b")?, ir::Value::Integer(2))?;
+
+        let error = scopes
+            .get_name_mut(&name("a")?, &Span::new(1, 2))
+            .unwrap_err();
+        assert_eq!(error.span(), Span::new(1, 2));
+        assert!(matches!(error.kind(), ErrorKind::AssignToConst { name } if &**name == "a"));
+
+        let error = scopes
+            .replace(&name("a")?, ir::Value::Integer(3), Span::empty())
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::AssignToConst { .. }));
+        assert!(matches!(
+            scopes.get_or_decl_with(&name("a")?, || ir::Value::Integer(3)),
+            Err(ErrorKind::AssignToConst { .. })
+        ));
+        assert!(scopes.try_get_mut(&name("a")?).is_none());
+
+        // Constants can still be read.
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(1));
+        *scopes.get_name_mut(&name("b")?, &Span::empty())? = ir::Value::Integer(4);
+        assert_eq!(scopes.try_get(&name("b")?).and_then(integer), Some(4));
+
+        // A mutable binding in an inner scope shadows the constant.
+        let guard = scopes.push()?;
+        scopes.decl_mut(&name("a")?, ir::Value::Integer(5))?;
+        *scopes.get_name_mut(&name("a")?, &Span::empty())? = ir::Value::Integer(6);
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(6));
+        scopes.pop(guard)?;
+
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(1));
+
+        // Redeclaring the constant in the same scope makes it mutable.
+        scopes.decl(&name("a")?, ir::Value::Integer(7))?;
+        *scopes.get_name_mut(&name("a")?, &Span::empty())? = ir::Value::Integer(8);
+        assert_eq!(scopes.try_get(&name("a")?).and_then(integer), Some(8));
+        Ok(())
+    }
+
+    #[test]
+    fn decl_const_rename() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl_const(&name("a")?, ir::Value::Integer(1))?;
+        scopes.rename(&name("a")?, &name("b")?, &Span::empty())?;
+
+        assert!(scopes.get_name_mut(&name("b")?, &Span::empty()).is_err());
+
+        scopes.remove(&name("b")?, &Span::empty())?;
+        scopes.decl(&name("b")?, ir::Value::Integer(2))?;
+        assert!(scopes.get_name_mut(&name("b")?, &Span::empty()).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn drain_current() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("outer")?, ir::Value::Integer(0))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl_const(&name("b")?, ir::Value::Integer(2))?;
+        scopes.decl(&name("c")?, ir::Value::Integer(3))?;
+
+        let mut drained = scopes.drain_current()?;
+        drained.sort_by_key(|(_, value)| integer(value));
+
+        assert_eq!(drained.len(), 3);
+
+        for ((local, value), (expected, n)) in drained.iter().zip([("a", 1), ("b", 2), ("c", 3)]) {
+            assert!(*local == name(expected)?);
+            assert_eq!(integer(value), Some(n));
+        }
+
+        assert!(scopes.current_is_empty());
+        assert_eq!(scopes.total_locals(), 1);
+
+        // The scope is reusable, and constants don't linger in it.
+        scopes.decl(&name("b")?, ir::Value::Integer(4))?;
+        assert!(scopes.get_name_mut(&name("b")?, &Span::empty()).is_ok());
+        assert_eq!(scopes.drain_current()?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn get_all() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+
+        let _first = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(2))?;
+
+        let _second = scopes.push()?;
+        scopes.decl(&name("b")?, ir::Value::Integer(3))?;
+
+        let _third = scopes.push()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(4))?;
+
+        let values = scopes.get_all(&name("a")?)?;
+        let values = values.into_iter().map(integer).try_collect::<Vec<_>>()?;
+        assert_eq!(values, [Some(4), Some(2), Some(1)]);
+
+        assert_eq!(scopes.get_all(&name("b")?)?.len(), 1);
+        assert!(scopes.get_all(&name("c")?)?.is_empty());
+
+        // Bindings behind an isolate scope aren't visible.
+        let _isolate = scopes.isolate()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(5))?;
+
+        let values = scopes.get_all(&name("a")?)?;
+        let values = values.into_iter().map(integer).try_collect::<Vec<_>>()?;
+        assert_eq!(values, [Some(5)]);
+        Ok(())
+    }
+
+    #[test]
+    fn find() -> Result<()> {
+        let mut scopes = Scopes::new()?;
+        scopes.decl(&name("a")?, ir::Value::Integer(1))?;
+        scopes.decl(&name("b")?, ir::Value::Integer(20))?;
+
+        let _guard = scopes.push()?;
+        scopes.decl(&name("c")?, ir::Value::Integer(30))?;
+
+        let found = scopes.find(|_, value| integer(value).is_some_and(|v| v >= 10));
+        assert!(matches!(found, Some(("c", ir::Value::Integer(30)))));
+
+        let found = scopes.find(|_, value| integer(value).is_some_and(|v| v < 10));
+        assert!(matches!(found, Some(("a", ir::Value::Integer(1)))));
+
+        // A shadowed binding is no longer found.
+        scopes.decl(&name("a")?, ir::Value::Integer(40))?;
+        let found = scopes.find(|_, value| integer(value).is_some_and(|v| v < 10));
+        assert!(found.is_none());
+        Ok(())
     }
 }
@@ -39,6 +39,10 @@ pub struct Options {
     pub(crate) v2: bool,
     /// Build sources as function bodies.
     pub(crate) function_body: bool,
+    /// Warn when a variable shadows a variable in an enclosing scope.
+    pub(crate) shadow_warnings: bool,
+    /// The maximum number of nested scopes when evaluating constants.
+    pub(crate) max_const_depth: Option<usize>,
 }
 
 impl Options {
@@ -76,6 +80,18 @@ impl Options {
             Some("function-body") => {
                 self.function_body = it.next() == Some("true");
             }
+            Some("shadow-warnings") => {
+                self.shadow_warnings = it.next() == Some("true");
+            }
+            Some("max-const-depth") => {
+                let Some(depth) = it.next().and_then(|value| value.parse().ok()) else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.max_const_depth = Some(depth);
+            }
             _ => {
                 return Err(ParseOptionError {
                     option: option.into(),
@@ -117,6 +133,19 @@ impl Options {
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
     }
+
+    /// Set if a warning should be emitted when a variable shadows a variable
+    /// declared in an enclosing scope. Defaults to `false`.
+    pub fn shadow_warnings(&mut self, enabled: bool) {
+        self.shadow_warnings = enabled;
+    }
+
+    /// Set the maximum number of nested scopes, including the root scope,
+    /// which can be used when evaluating constants. Defaults to `None`, which
+    /// means that the depth is not limited.
+    pub fn max_const_depth(&mut self, max_const_depth: Option<usize>) {
+        self.max_const_depth = max_const_depth;
+    }
 }
 
 impl Default for Options {
@@ -130,6 +159,8 @@ impl Default for Options {
             cfg_test: false,
             v2: false,
             function_body: false,
+            shadow_warnings: false,
+            max_const_depth: None,
         }
     }
 }
@@ -2,7 +2,7 @@ pub(crate) mod assemble;
 pub(crate) use self::assemble::{Ctxt, Needs};
 
 mod loops;
-pub(crate) use self::loops::{Loop, Loops};
+pub(crate) use self::loops::{Loop, LoopKind, Loops};
 
 mod scopes;
 pub(crate) use self::scopes::{Layer, ScopeGuard, Scopes, Var};
@@ -5,7 +5,7 @@ use crate::alloc::prelude::*;
 use crate::alloc::{try_format, Vec};
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
-use crate::compile::v1::{Layer, Loop, Loops, ScopeGuard, Scopes, Var};
+use crate::compile::v1::{Layer, Loop, LoopKind, Loops, ScopeGuard, Scopes, Var};
 use crate::compile::{self, Assembly, ErrorKind, ItemId, ModId, Options, WithSpan};
 use crate::hir;
 use crate::query::{ConstFn, Query, Used};
@@ -149,7 +149,7 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
 
         let mut interpreter = ir::Interpreter {
             budget: ir::Budget::new(1_000_000),
-            scopes: ir::Scopes::new()?,
+            scopes: ir::Scopes::with_max_depth(self.q.options.max_const_depth)?,
             module: from_module,
             item: from_item,
             q: self.q.borrow(),
@@ -681,6 +681,28 @@ fn block<'hir>(
     hir: &hir::Block<'hir>,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    // NB: a labeled block can be broken out of like a loop.
+    let break_label = match hir.label {
+        Some(label) => {
+            let break_label = cx.asm.new_label("block_break");
+            let var_count = cx.scopes.total(hir)?;
+
+            cx.loops.push(Loop {
+                label: Some(label),
+                continue_label: None,
+                continue_var_count: var_count,
+                break_label: break_label.try_clone()?,
+                break_var_count: var_count,
+                needs,
+                drop: None,
+                kind: LoopKind::Loop,
+            })?;
+
+            Some(break_label)
+        }
+        None => None,
+    };
+
     cx.contexts.try_push(hir.span())?;
     let scopes_count = cx.scopes.child(hir)?;
 
@@ -738,6 +760,12 @@ fn block<'hir>(
         .ok_or("Missing parent context")
         .with_span(hir)?;
 
+    if let Some(break_label) = break_label {
+        // NB: breaks produce their own value / perform their own cleanup.
+        cx.asm.label(&break_label)?;
+        cx.loops.pop();
+    }
+
     Ok(Asm::top(hir))
 }
 
@@ -1312,31 +1340,47 @@ fn expr_break<'hir>(
     span: &dyn Spanned,
     _: Needs,
 ) -> compile::Result<Asm<'hir>> {
-    let Some(current_loop) = cx.loops.last().try_cloned()? else {
-        return Err(compile::Error::new(span, ErrorKind::BreakOutsideOfLoop));
-    };
-
-    let (last_loop, to_drop, has_value) = match (hir.label, hir.expr) {
-        (None, Some(e)) => {
-            expr(cx, e, current_loop.needs)?.apply(cx)?;
-            let to_drop = current_loop.drop.into_iter().try_collect()?;
-            (current_loop, to_drop, true)
-        }
-        (Some(label), None) => {
-            let (last_loop, to_drop) = cx.loops.walk_until_label(label, span)?;
-            (last_loop.try_clone()?, to_drop, false)
-        }
-        (Some(label), Some(e)) => {
-            expr(cx, e, current_loop.needs)?.apply(cx)?;
+    let (last_loop, to_drop) = match hir.label {
+        Some(label) => {
             let (last_loop, to_drop) = cx.loops.walk_until_label(label, span)?;
-            (last_loop.try_clone()?, to_drop, true)
+
+            if last_loop.kind.is_conditional() && hir.expr.is_some() {
+                return Err(compile::Error::new(
+                    span,
+                    ErrorKind::BreakValueInConditionLoop {
+                        label: label.try_into()?,
+                    },
+                ));
+            }
+
+            (last_loop.try_clone()?, to_drop)
         }
-        (None, None) => {
+        None => {
+            let Some(current_loop) = cx.loops.last().try_cloned()? else {
+                return Err(compile::Error::new(span, ErrorKind::BreakOutsideOfLoop));
+            };
+
+            if current_loop.kind == LoopKind::While && hir.expr.is_some() {
+                return Err(compile::Error::new(
+                    span,
+                    ErrorKind::BreakDoesNotProduceValue,
+                ));
+            }
+
             let to_drop = current_loop.drop.into_iter().try_collect()?;
-            (current_loop, to_drop, false)
+            (current_loop, to_drop)
         }
     };
 
+    // NB: the value is produced for the loop being broken out of, which for a
+    // labeled break isn't necessarily the innermost one.
+    let has_value = if let Some(e) = hir.expr {
+        expr(cx, e, last_loop.needs)?.apply(cx)?;
+        true
+    } else {
+        false
+    };
+
     // Drop loop temporaries. Typically an iterator.
     for offset in to_drop {
         cx.asm.push(Inst::Drop { offset }, span)?;
@@ -1481,6 +1525,10 @@ fn expr_call_closure<'hir>(
 }
 
 /// Assemble a continue expression.
+///
+/// NB: continue diverges, so it never has to produce a value even when it's
+/// used in a position where one is expected, like `None => continue` in a
+/// match arm.
 #[instrument(span = span)]
 fn expr_continue<'hir>(
     cx: &mut Ctxt<'_, 'hir, '_>,
@@ -1488,14 +1536,14 @@ fn expr_continue<'hir>(
     span: &dyn Spanned,
     _: Needs,
 ) -> compile::Result<Asm<'hir>> {
-    let Some(current_loop) = cx.loops.last().try_cloned()? else {
-        return Err(compile::Error::new(span, ErrorKind::ContinueOutsideOfLoop));
-    };
-
     let last_loop = if let Some(label) = hir.label {
         let (last_loop, _) = cx.loops.walk_until_label(label, span)?;
         last_loop.try_clone()?
     } else {
+        let Some(current_loop) = cx.loops.last().try_cloned()? else {
+            return Err(compile::Error::new(span, ErrorKind::ContinueOutsideOfLoop));
+        };
+
         current_loop
     };
 
@@ -1508,7 +1556,13 @@ fn expr_continue<'hir>(
 
     cx.locals_pop(vars, span)?;
 
-    cx.asm.jump(&last_loop.continue_label, span)?;
+    let continue_label = last_loop
+        .continue_label
+        .as_ref()
+        .ok_or("Labeled blocks can't be continued")
+        .with_span(span)?;
+
+    cx.asm.jump(continue_label, span)?;
     Ok(Asm::top(span))
 }
 
@@ -1648,12 +1702,13 @@ fn expr_for<'hir>(
 
     cx.loops.push(Loop {
         label: hir.label,
-        continue_label: continue_label.try_clone()?,
+        continue_label: Some(continue_label.try_clone()?),
         continue_var_count,
         break_label: break_label.try_clone()?,
         break_var_count,
         needs,
         drop: Some(iter_offset),
+        kind: LoopKind::For,
     })?;
 
     // Use the memoized loop variable.
@@ -2408,12 +2463,17 @@ fn expr_loop<'hir>(
 
     cx.loops.push(Loop {
         label: hir.label,
-        continue_label: continue_label.try_clone()?,
+        continue_label: Some(continue_label.try_clone()?),
         continue_var_count: var_count,
         break_label: break_label.try_clone()?,
         break_var_count: var_count,
         needs,
         drop: None,
+        kind: if hir.condition.is_some() {
+            LoopKind::While
+        } else {
+            LoopKind::Loop
+        },
     })?;
 
     cx.asm.label(&continue_label)?;
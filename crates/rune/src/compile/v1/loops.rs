@@ -6,13 +6,36 @@ use crate::compile::v1::Needs;
 use crate::compile::{self, ErrorKind};
 use crate::runtime::Label;
 
+/// The kind of a loop.
+#[derive(Debug, TryClone, Clone, Copy, PartialEq, Eq)]
+#[try_clone(copy)]
+pub(crate) enum LoopKind {
+    /// A `loop` or a labeled block, which can only complete by being broken
+    /// out of.
+    Loop,
+    /// A `while` loop.
+    While,
+    /// A `for` loop.
+    For,
+}
+
+impl LoopKind {
+    /// Test if the loop is a `while` or `for` loop, which might complete
+    /// without being broken out of.
+    pub(crate) fn is_conditional(self) -> bool {
+        matches!(self, LoopKind::While | LoopKind::For)
+    }
+}
+
 /// Loops we are inside.
 #[derive(TryClone)]
 pub(crate) struct Loop<'hir> {
     /// The optional label of the start of the loop.
     pub(crate) label: Option<&'hir str>,
     /// The start label of the loop, used for `continue`.
-    pub(crate) continue_label: Label,
+    ///
+    /// Labeled blocks can't be continued, so they don't have one.
+    pub(crate) continue_label: Option<Label>,
     /// The number of local variables inside the loop.
     pub(crate) continue_var_count: usize,
     /// The end label of the loop, used for `break`.
@@ -23,6 +46,8 @@ pub(crate) struct Loop<'hir> {
     pub(crate) needs: Needs,
     /// Locals to drop when breaking.
     pub(crate) drop: Option<usize>,
+    /// The kind of the loop.
+    pub(crate) kind: LoopKind,
 }
 
 pub(crate) struct Loops<'hir> {
@@ -35,9 +60,9 @@ impl<'hir> Loops<'hir> {
         Self { loops: Vec::new() }
     }
 
-    /// Get the last loop context.
+    /// Get the last loop context, skipping over any labeled blocks.
     pub(crate) fn last(&self) -> Option<&Loop<'hir>> {
-        self.loops.last()
+        self.loops.iter().rev().find(|l| l.continue_label.is_some())
     }
 
     /// Push loop information.
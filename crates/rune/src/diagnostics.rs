@@ -278,6 +278,63 @@ impl Diagnostics {
         )
     }
 
+    /// Add a warning about a variable shadowing a variable declared in an
+    /// enclosing scope.
+    pub(crate) fn shadowed_variable(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::ShadowedVariable { span: span.span() },
+        )
+    }
+
+    /// Add a warning about a loop label shadowing the label of an enclosing
+    /// loop.
+    pub(crate) fn duplicate_loop_label(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::DuplicateLoopLabel { span: span.span() },
+        )
+    }
+
+    /// Add a warning about the use of a deprecated function.
+    pub(crate) fn used_deprecated(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        context: Option<Span>,
+        message: &'static str,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UsedDeprecated {
+                span: span.span(),
+                context,
+                message,
+            },
+        )
+    }
+
+    /// Add a warning about a statement which can't be reached, since it follows
+    /// an unconditional `break`, `continue` or `return`.
+    pub(crate) fn unreachable_after_break(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnreachableAfterBreak { span: span.span() },
+        )
+    }
+
     /// Push a warning to the collection of diagnostics.
     pub(crate) fn warning<T>(&mut self, source_id: SourceId, kind: T) -> alloc::Result<()>
     where
@@ -653,6 +653,13 @@ where
                         .with_message("Nested in here"),
                 );
             }
+            ErrorKind::BreakValueInConditionLoop { .. } => {
+                notes.push(
+                    "Labeled breaks can only carry a value out of a `loop` or a labeled block"
+                        .try_to_string()?
+                        .into_std(),
+                );
+            }
             ErrorKind::PatternMissingFields { fields, .. } => {
                 let pl = if fields.len() == 1 { "field" } else { "fields" };
 
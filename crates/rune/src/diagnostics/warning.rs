@@ -38,8 +38,12 @@ impl WarningDiagnostic {
             WarningDiagnosticKind::LetPatternMightPanic { context, .. }
             | WarningDiagnosticKind::RemoveTupleCallParams { context, .. }
             | WarningDiagnosticKind::NotUsed { context, .. }
-            | WarningDiagnosticKind::TemplateWithoutExpansions { context, .. } => *context,
-            WarningDiagnosticKind::UnnecessarySemiColon { .. } => None,
+            | WarningDiagnosticKind::TemplateWithoutExpansions { context, .. }
+            | WarningDiagnosticKind::UsedDeprecated { context, .. } => *context,
+            WarningDiagnosticKind::UnnecessarySemiColon { .. }
+            | WarningDiagnosticKind::ShadowedVariable { .. }
+            | WarningDiagnosticKind::DuplicateLoopLabel { .. }
+            | WarningDiagnosticKind::UnreachableAfterBreak { .. } => None,
         }
     }
 }
@@ -53,6 +57,10 @@ impl Spanned for WarningDiagnostic {
             WarningDiagnosticKind::TemplateWithoutExpansions { span, .. } => *span,
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
+            WarningDiagnosticKind::ShadowedVariable { span, .. } => *span,
+            WarningDiagnosticKind::DuplicateLoopLabel { span, .. } => *span,
+            WarningDiagnosticKind::UnreachableAfterBreak { span, .. } => *span,
+            WarningDiagnosticKind::UsedDeprecated { span, .. } => *span,
         }
     }
 }
@@ -114,6 +122,31 @@ pub enum WarningDiagnosticKind {
         /// Span where the semi-colon is.
         span: Span,
     },
+    /// A variable shadows a variable declared in an enclosing scope.
+    ShadowedVariable {
+        /// Span of the variable which shadows another.
+        span: Span,
+    },
+    /// A loop label shadows the label of an enclosing loop.
+    DuplicateLoopLabel {
+        /// Span of the inner label.
+        span: Span,
+    },
+    /// A statement follows an unconditional `break`, `continue` or `return`
+    /// in the same block.
+    UnreachableAfterBreak {
+        /// Span of the first unreachable statement.
+        span: Span,
+    },
+    /// A deprecated function was used.
+    UsedDeprecated {
+        /// The span of the use.
+        span: Span,
+        /// The context in which it is used.
+        context: Option<Span>,
+        /// The deprecation message of the function.
+        message: &'static str,
+    },
 }
 
 impl fmt::Display for WarningDiagnosticKind {
@@ -133,6 +166,18 @@ impl fmt::Display for WarningDiagnosticKind {
             WarningDiagnosticKind::UnnecessarySemiColon { .. } => {
                 write!(f, "Unnecessary semicolon")
             }
+            WarningDiagnosticKind::ShadowedVariable { .. } => {
+                write!(f, "Variable shadows a variable in an enclosing scope")
+            }
+            WarningDiagnosticKind::DuplicateLoopLabel { .. } => {
+                write!(f, "Loop label shadows the label of an enclosing loop")
+            }
+            WarningDiagnosticKind::UnreachableAfterBreak { .. } => {
+                write!(f, "Unreachable code after `break`, `continue` or `return`")
+            }
+            WarningDiagnosticKind::UsedDeprecated { message, .. } => {
+                write!(f, "Used deprecated function: {message}")
+            }
         }
     }
 }
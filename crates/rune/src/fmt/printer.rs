@@ -1745,6 +1745,7 @@ impl<'a> Printer<'a> {
     fn visit_expr_block(&mut self, ast: &ast::ExprBlock) -> Result<()> {
         let ast::ExprBlock {
             attributes,
+            label,
             async_token,
             const_token,
             move_token,
@@ -1755,6 +1756,11 @@ impl<'a> Printer<'a> {
             self.visit_attribute(attribute)?;
         }
 
+        if let Some((label, colon)) = label {
+            self.writer.write_spanned_raw(label.span, false, false)?;
+            self.writer.write_spanned_raw(colon.span, false, true)?;
+        }
+
         if let Some(async_token) = async_token {
             self.writer
                 .write_spanned_raw(async_token.span, false, true)?;
@@ -716,6 +716,8 @@ pub(crate) struct Block<'hir> {
     /// The span of the block.
     #[rune(span)]
     pub(crate) span: Span,
+    /// The label of the block, if it's a labeled block expression.
+    pub(crate) label: Option<&'hir str>,
     /// Statements in the block.
     pub(crate) statements: &'hir [Stmt<'hir>],
     /// Variables that need to be dropped by the end of this block.
@@ -36,6 +36,11 @@ pub(crate) struct Ctxt<'hir, 'a, 'arena> {
     needs: Cell<Needs>,
     scopes: hir::Scopes<'hir>,
     const_eval: bool,
+    /// Set if lowering recovered from any errors.
+    has_errors: bool,
+    /// The number of closure or async block bodies being lowered to infer
+    /// their captures.
+    inferring_captures: usize,
 }
 
 impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
@@ -85,9 +90,58 @@ impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
             needs: Cell::new(Needs::default()),
             scopes: hir::Scopes::new()?,
             const_eval,
+            has_errors: false,
+            inferring_captures: 0,
         })
     }
 
+    /// Test if lowering recovered from any errors, which have already been
+    /// reported to diagnostics.
+    ///
+    /// The lowered tree shouldn't be assembled if this is set, since it only
+    /// approximates the erroneous source.
+    pub(crate) fn has_errors(&self) -> bool {
+        self.has_errors
+    }
+
+    /// Lower the body of a closure or async block to infer its captures.
+    ///
+    /// The body is lowered again on its own when it's built, so diagnostics
+    /// are only reported in that pass to avoid reporting them twice.
+    fn infer_captures<F, O>(&mut self, f: F) -> O
+    where
+        F: FnOnce(&mut Self) -> O,
+    {
+        self.inferring_captures += 1;
+        let output = f(self);
+        self.inferring_captures -= 1;
+        output
+    }
+
+    /// Test if recovered errors and warnings should be reported, which isn't
+    /// the case while inferring captures.
+    fn reports_diagnostics(&self) -> bool {
+        self.inferring_captures == 0
+    }
+
+    /// Report an error which lowering can recover from, so that further errors
+    /// can be reported in the same pass.
+    ///
+    /// In a constant context the error is returned instead, since the lowered
+    /// tree is evaluated immediately.
+    fn recover(&mut self, error: compile::Error) -> compile::Result<()> {
+        if self.const_eval {
+            return Err(error);
+        }
+
+        if self.reports_diagnostics() {
+            self.q.diagnostics.error(self.source_id, error)?;
+            self.has_errors = true;
+        }
+
+        Ok(())
+    }
+
     #[allow(unused)]
     #[instrument(span = ast)]
     pub(crate) fn try_lookup_meta(
@@ -129,6 +183,7 @@ pub(crate) fn empty_fn<'hir>(
 
     let body = hir::Block {
         span: span.span(),
+        label: None,
         statements,
         drop: iter!(layer.into_drop_order()),
     };
@@ -263,11 +318,14 @@ fn expr_call_closure<'hir>(
 
             cx.scopes.push_captures()?;
 
-            for (arg, _) in ast.args.as_slice() {
-                fn_arg(cx, arg)?;
-            }
+            cx.infer_captures(|cx| {
+                for (arg, _) in ast.args.as_slice() {
+                    fn_arg(cx, arg)?;
+                }
+
+                expr(cx, &ast.body)
+            })?;
 
-            expr(cx, &ast.body)?;
             let layer = cx.scopes.pop().with_span(&ast.body)?;
 
             cx.q.set_used(&meta.item_meta)?;
@@ -309,6 +367,8 @@ pub(crate) fn block<'hir>(
 ) -> compile::Result<hir::Block<'hir>> {
     alloc_with!(cx, ast);
 
+    warn_unreachable(cx, &ast.statements)?;
+
     cx.scopes.push()?;
 
     let statements = iter!(&ast.statements, |ast| stmt(cx, ast)?);
@@ -317,6 +377,7 @@ pub(crate) fn block<'hir>(
 
     let block = hir::Block {
         span: ast.span(),
+        label: None,
         statements,
         drop: iter!(layer.into_drop_order()),
     };
@@ -324,6 +385,40 @@ pub(crate) fn block<'hir>(
     Ok(block)
 }
 
+/// Warn about the first statement following an unconditional `break`,
+/// `continue` or `return`, since it can never be reached.
+fn warn_unreachable(cx: &mut Ctxt<'_, '_, '_>, statements: &[ast::Stmt]) -> compile::Result<()> {
+    if !cx.reports_diagnostics() {
+        return Ok(());
+    }
+
+    let mut diverged = false;
+
+    for stmt in statements {
+        // NB: items are declarations, so they're not affected by control flow.
+        if let ast::Stmt::Item(..) = stmt {
+            continue;
+        }
+
+        if diverged {
+            cx.q.diagnostics
+                .unreachable_after_break(cx.source_id, stmt)?;
+            break;
+        }
+
+        let (ast::Stmt::Expr(expr) | ast::Stmt::Semi(ast::StmtSemi { expr, .. })) = stmt else {
+            continue;
+        };
+
+        diverged = matches!(
+            expr,
+            ast::Expr::Break(..) | ast::Expr::Continue(..) | ast::Expr::Return(..)
+        );
+    }
+
+    Ok(())
+}
+
 #[instrument(span = ast)]
 pub(crate) fn expr_range<'hir>(
     cx: &mut Ctxt<'hir, '_, '_>,
@@ -524,7 +619,7 @@ pub(crate) fn expr<'hir>(
                 None => None,
             };
 
-            cx.scopes.push_loop(label)?;
+            push_loop(cx, label, &ast.label)?;
             let condition = condition(cx, &ast.condition)?;
             let body = block(cx, &ast.body)?;
             let layer = cx.scopes.pop().with_span(ast)?;
@@ -542,7 +637,7 @@ pub(crate) fn expr<'hir>(
                 None => None,
             };
 
-            cx.scopes.push_loop(label)?;
+            push_loop(cx, label, &ast.label)?;
             let body = block(cx, &ast.body)?;
             let layer = cx.scopes.pop().with_span(ast)?;
 
@@ -563,7 +658,7 @@ pub(crate) fn expr<'hir>(
                 None => None,
             };
 
-            cx.scopes.push_loop(label)?;
+            push_loop(cx, label, &ast.label)?;
             let binding = pat(cx, &ast.binding)?;
             let body = block(cx, &ast.body)?;
 
@@ -892,7 +987,19 @@ pub(crate) fn expr_block<'hir>(
     };
 
     if let ExprBlockKind::Default = kind {
-        return Ok(hir::ExprKind::Block(alloc!(block(cx, &ast.block)?)));
+        let Some((label, _)) = &ast.label else {
+            return Ok(hir::ExprKind::Block(alloc!(block(cx, &ast.block)?)));
+        };
+
+        let label = alloc_str!(label.resolve(resolve_context!(cx.q))?);
+
+        warn_duplicate_label(cx, label, &ast.label)?;
+        cx.scopes.push_block(label)?;
+        let mut body = block(cx, &ast.block)?;
+        cx.scopes.pop().with_span(ast)?;
+
+        body.label = Some(label);
+        return Ok(hir::ExprKind::Block(alloc!(body)));
     }
 
     if cx.const_eval {
@@ -919,7 +1026,7 @@ pub(crate) fn expr_block<'hir>(
                     tracing::trace!("queuing async block build entry");
 
                     cx.scopes.push_captures()?;
-                    block(cx, &ast.block)?;
+                    cx.infer_captures(|cx| block(cx, &ast.block))?;
                     let layer = cx.scopes.pop().with_span(&ast.block)?;
 
                     cx.q.insert_captures(meta.hash, layer.captures())?;
@@ -967,29 +1074,34 @@ fn expr_break<'hir>(
 ) -> compile::Result<hir::ExprBreak<'hir>> {
     alloc_with!(cx, ast);
 
+    // NB: the label is copied into the arena, so that it doesn't borrow the
+    // context while errors are recovered from.
     let label = match &ast.label {
-        Some(label) => Some(label.resolve(resolve_context!(cx.q))?),
+        Some(label) => Some(alloc_str!(label.resolve(resolve_context!(cx.q))?)),
         None => None,
     };
 
-    let Some(drop) = cx.scopes.loop_drop(label)? else {
-        if let Some(label) = label {
-            return Err(compile::Error::new(
-                ast,
-                ErrorKind::MissingLoopLabel {
-                    label: label.try_into()?,
-                },
-            ));
-        } else {
-            return Err(compile::Error::new(ast, ErrorKind::BreakOutsideOfLoop));
+    let drop = match cx.scopes.loop_drop(label)? {
+        Some(drop) => drop,
+        None => {
+            let error = if let (Some(span), Some(label)) = (&ast.label, label) {
+                compile::Error::new(
+                    span,
+                    ErrorKind::BreakMissingLabel {
+                        label: label.try_into()?,
+                    },
+                )
+            } else {
+                compile::Error::new(ast.break_token, ErrorKind::BreakOutsideOfLoop)
+            };
+
+            cx.recover(error)?;
+            alloc::Vec::new()
         }
     };
 
     Ok(hir::ExprBreak {
-        label: match label {
-            Some(label) => Some(alloc_str!(label)),
-            None => None,
-        },
+        label,
         expr: match &ast.expr {
             Some(ast) => Some(alloc!(expr(cx, ast)?)),
             None => None,
@@ -1001,34 +1113,53 @@ fn expr_break<'hir>(
 /// Unroll a continue expression, capturing all variables which are in scope at
 /// the time of it.
 fn expr_continue<'hir>(
-    cx: &Ctxt<'hir, '_, '_>,
+    cx: &mut Ctxt<'hir, '_, '_>,
     ast: &ast::ExprContinue,
 ) -> compile::Result<hir::ExprContinue<'hir>> {
     alloc_with!(cx, ast);
 
     let label = match &ast.label {
-        Some(label) => Some(label.resolve(resolve_context!(cx.q))?),
+        Some(label) => Some(alloc_str!(label.resolve(resolve_context!(cx.q))?)),
         None => None,
     };
 
-    let Some(drop) = cx.scopes.loop_drop(label)? else {
-        if let Some(label) = label {
-            return Err(compile::Error::new(
+    if let Some(label) = label {
+        if cx.scopes.is_block_label(label) {
+            cx.recover(compile::Error::new(
                 ast,
-                ErrorKind::MissingLoopLabel {
+                ErrorKind::ContinueLabeledBlock {
                     label: label.try_into()?,
                 },
-            ));
-        } else {
-            return Err(compile::Error::new(ast, ErrorKind::ContinueOutsideOfLoop));
+            ))?;
+
+            return Ok(hir::ExprContinue {
+                label: Some(label),
+                drop: &[],
+            });
+        }
+    }
+
+    let drop = match cx.scopes.loop_drop(label)? {
+        Some(drop) => drop,
+        None => {
+            let error = if let Some(label) = label {
+                compile::Error::new(
+                    ast,
+                    ErrorKind::ContinueMissingLabel {
+                        label: label.try_into()?,
+                    },
+                )
+            } else {
+                compile::Error::new(ast, ErrorKind::ContinueOutsideOfLoop)
+            };
+
+            cx.recover(error)?;
+            alloc::Vec::new()
         }
     };
 
     Ok(hir::ExprContinue {
-        label: match label {
-            Some(label) => Some(alloc_str!(label)),
-            None => None,
-        },
+        label,
         drop: iter!(drop),
     })
 }
@@ -1075,6 +1206,57 @@ fn stmt<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Stmt) -> compile::Result<h
     })
 }
 
+/// Push a loop scope, warning if its label shadows the label of an enclosing
+/// loop.
+fn push_loop<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    label: Option<&'hir str>,
+    ast: &Option<(ast::Label, T![:])>,
+) -> compile::Result<()> {
+    if let Some(label) = label {
+        warn_duplicate_label(cx, label, ast)?;
+    }
+
+    cx.scopes.push_loop(label)?;
+    Ok(())
+}
+
+/// Warn if the given label shadows the label of an enclosing loop or block.
+fn warn_duplicate_label(
+    cx: &mut Ctxt<'_, '_, '_>,
+    label: &str,
+    ast: &Option<(ast::Label, T![:])>,
+) -> compile::Result<()> {
+    if !cx.reports_diagnostics() {
+        return Ok(());
+    }
+
+    if let Some((span, _)) = ast {
+        if cx.scopes.has_loop_label(label) {
+            cx.q.diagnostics.duplicate_loop_label(cx.source_id, span)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Define a variable bound by a pattern.
+///
+/// If enabled, this warns when the variable shadows a variable declared in an
+/// enclosing scope.
+fn define_binding<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    name: hir::Name<'hir>,
+    span: &dyn Spanned,
+) -> compile::Result<()> {
+    if cx.q.options.shadow_warnings && cx.reports_diagnostics() && cx.scopes.shadows(name) {
+        cx.q.diagnostics.shadowed_variable(cx.source_id, span)?;
+    }
+
+    cx.scopes.define(name, span)?;
+    Ok(())
+}
+
 fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir::Pat<'hir>> {
     fn filter((ast, _): &(ast::Pat, Option<ast::Comma>)) -> Option<&ast::Pat> {
         if matches!(ast, ast::Pat::Binding(..) | ast::Pat::Rest(..)) {
@@ -1101,7 +1283,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
 
                 if let Some(ident) = ast.path.try_as_ident() {
                     let name = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
-                    cx.scopes.define(hir::Name::Str(name), ast)?;
+                    define_binding(cx, hir::Name::Str(name), ast)?;
                     break 'ok hir::PatPathKind::Ident(name);
                 }
 
@@ -1199,7 +1381,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                         };
 
                         let key = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
-                        cx.scopes.define(hir::Name::Str(key), ident)?;
+                        define_binding(cx, hir::Name::Str(key), ident)?;
                         (key, hir::Binding::Ident(path.span(), key))
                     }
                     _ => {
@@ -1691,7 +1873,18 @@ fn expr_call<'hir>(
                             )?;
                         }
                     }
-                    meta::Kind::Function { .. } => (),
+                    meta::Kind::Function { .. } => {
+                        if cx.reports_diagnostics() {
+                            if let Some(message) = cx.q.context.lookup_deprecation(meta.hash) {
+                                cx.q.diagnostics.used_deprecated(
+                                    cx.source_id,
+                                    path,
+                                    None,
+                                    message,
+                                )?;
+                            }
+                        }
+                    }
                     meta::Kind::ConstFn { id, .. } => {
                         let id = *id;
                         let from = cx.q.item_for(ast.id).with_span(ast)?;
@@ -1721,10 +1914,12 @@ fn expr_call<'hir>(
                     hir::ExprField::Index(index) => Hash::index(index),
                     hir::ExprField::Ident(ident) => {
                         cx.q.unit.insert_debug_ident(ident)?;
+                        warn_deprecated_instance_fn(cx, &ast.expr, ident)?;
                         Hash::ident(ident)
                     }
                     hir::ExprField::IdentGenerics(ident, hash) => {
                         cx.q.unit.insert_debug_ident(ident)?;
+                        warn_deprecated_instance_fn(cx, &ast.expr, ident)?;
                         Hash::ident(ident).with_function_parameters(hash)
                     }
                 };
@@ -1746,6 +1941,25 @@ fn expr_call<'hir>(
     })
 }
 
+/// Warn about an instance function call, if every native instance function
+/// with the given name is deprecated.
+fn warn_deprecated_instance_fn(
+    cx: &mut Ctxt<'_, '_, '_>,
+    span: &dyn Spanned,
+    ident: &str,
+) -> compile::Result<()> {
+    if !cx.reports_diagnostics() {
+        return Ok(());
+    }
+
+    if let Some(message) = cx.q.context.lookup_instance_deprecation(Hash::ident(ident)) {
+        cx.q.diagnostics
+            .used_deprecated(cx.source_id, span, None, message)?;
+    }
+
+    Ok(())
+}
+
 #[instrument(span = ast)]
 fn expr_field_access<'hir>(
     cx: &mut Ctxt<'hir, '_, '_>,
@@ -23,6 +23,7 @@ enum LayerKind {
     #[default]
     Default,
     Loop,
+    Block,
     Captures,
 }
 
@@ -96,6 +97,11 @@ impl<'hir> Scopes<'hir> {
         self.push_kind(LayerKind::Loop, label)
     }
 
+    /// Push a labeled block.
+    pub(crate) fn push_block(&mut self, label: &'hir str) -> alloc::Result<()> {
+        self.push_kind(LayerKind::Block, Some(label))
+    }
+
     fn push_kind(&mut self, kind: LayerKind, label: Option<&'hir str>) -> alloc::Result<()> {
         let scope = Scope(self.scopes.len());
 
@@ -153,6 +159,60 @@ impl<'hir> Scopes<'hir> {
         Ok(name)
     }
 
+    /// Test if the given variable is defined in a scope enclosing the current
+    /// one.
+    ///
+    /// Variables defined in the current scope are not considered.
+    pub(crate) fn shadows(&self, name: hir::Name<'hir>) -> bool {
+        let Some(layer) = self.scopes.get(self.scope.0) else {
+            return false;
+        };
+
+        let mut scope = layer.parent().and_then(|parent| self.scopes.get(parent));
+
+        while let Some(layer) = scope.take() {
+            if layer.variables.contains(&name) {
+                return true;
+            }
+
+            scope = layer.parent().and_then(|parent| self.scopes.get(parent));
+        }
+
+        false
+    }
+
+    /// Test if the given label is used by the current layer or any of its
+    /// parents.
+    pub(crate) fn has_loop_label(&self, label: &str) -> bool {
+        let mut scope = self.scopes.get(self.scope.0);
+
+        while let Some(layer) = scope.take() {
+            if layer.label == Some(label) {
+                return true;
+            }
+
+            scope = layer.parent().and_then(|parent| self.scopes.get(parent));
+        }
+
+        false
+    }
+
+    /// Test if the innermost layer using the given label is a labeled block
+    /// rather than a loop.
+    pub(crate) fn is_block_label(&self, label: &str) -> bool {
+        let mut scope = self.scopes.get(self.scope.0);
+
+        while let Some(layer) = scope.take() {
+            if layer.label == Some(label) {
+                return matches!(layer.kind, LayerKind::Block);
+            }
+
+            scope = layer.parent().and_then(|parent| self.scopes.get(parent));
+        }
+
+        false
+    }
+
     /// Try to lookup the given variable.
     #[tracing::instrument(skip_all, fields(?self.scope, ?name))]
     pub(crate) fn get(
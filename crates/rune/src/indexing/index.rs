@@ -901,6 +901,13 @@ fn expr_block(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprBlock) -> compile::R
         return block(idx, &mut ast.block);
     }
 
+    if let Some(span) = ast.label.option_span() {
+        return Err(compile::Error::msg(
+            span,
+            "Labels are only supported on plain blocks",
+        ));
+    }
+
     let guard = idx.items.push_id()?;
     let idx_item = idx.item.replace();
 
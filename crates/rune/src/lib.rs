@@ -572,6 +572,19 @@ pub(crate) use rune_macros::__internal_impl_any;
 /// }
 /// ```
 ///
+/// # Deprecating functions
+///
+/// A function can be marked as deprecated with the `deprecated` option. Calls
+/// to the function from scripts then produce a warning including the given
+/// message.
+///
+/// ```
+/// #[rune::function(deprecated = "Use `add` instead")]
+/// fn plus(a: i64, b: i64) -> i64 {
+///     a + b
+/// }
+/// ```
+///
 /// [`VmResult`]: crate::runtime::VmResult
 /// [`vm_try!`]: crate::vm_try!
 pub use rune_macros::function;
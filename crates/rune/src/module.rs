@@ -16,14 +16,17 @@ use crate as rune;
 use crate::alloc::prelude::*;
 #[cfg(feature = "doc")]
 use crate::alloc::Box;
-use crate::alloc::{self, Vec};
+use crate::alloc::{self, HashSet, Vec};
 use crate::compile::{meta, ContextError, Docs, Item, ItemBuf};
 use crate::runtime::{
     AttributeMacroHandler, ConstValue, FullTypeOf, FunctionHandler, MacroHandler, MaybeTypeOf,
-    StaticType, TypeCheck, TypeInfo, TypeOf,
+    Protocol, StaticType, TypeCheck, TypeInfo, TypeOf, UnsafeToRef,
 };
 use crate::Hash;
 
+use self::function_meta::{AssociatedFunctionData, FunctionArgs};
+use self::module::Name;
+
 pub(crate) use self::function_meta::{AssociatedName, ToFieldFunction, ToInstance};
 
 #[doc(hidden)]
@@ -82,7 +85,6 @@ impl InternalEnum {
             type_check: Some(type_check),
             fields: Some(Fields::Unnamed(C::args())),
             constructor: Some(constructor),
-            #[cfg(feature = "doc")]
             deprecated: None,
             docs: Docs::EMPTY,
         })?;
@@ -91,7 +93,6 @@ impl InternalEnum {
 
         Ok(ItemMut {
             docs: &mut v.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut v.deprecated,
         })
     }
@@ -138,8 +139,7 @@ pub struct Variant {
     pub(crate) fields: Option<Fields>,
     /// Handler to use if this variant can be constructed through a regular function call.
     pub(crate) constructor: Option<Arc<FunctionHandler>>,
-    #[cfg(feature = "doc")]
-    pub(crate) deprecated: Option<Box<str>>,
+    pub(crate) deprecated: Option<&'static str>,
     /// Variant documentation.
     pub(crate) docs: Docs,
 }
@@ -151,7 +151,6 @@ impl Variant {
             type_check: None,
             fields: None,
             constructor: None,
-            #[cfg(feature = "doc")]
             deprecated: None,
             docs: Docs::EMPTY,
         }
@@ -163,7 +162,6 @@ impl fmt::Debug for Variant {
         let mut f = f.debug_struct("Variant");
         f.field("fields", &self.fields);
         f.field("constructor", &self.constructor.is_some());
-        #[cfg(feature = "doc")]
         f.field("deprecated", &self.deprecated);
         f.field("docs", &self.docs);
         f.finish()
@@ -232,8 +230,7 @@ pub(crate) struct ModuleItemCommon {
     /// Documentation for the item.
     pub(crate) docs: Docs,
     /// Deprecation marker for the item.
-    #[cfg(feature = "doc")]
-    pub(crate) deprecated: Option<Box<str>>,
+    pub(crate) deprecated: Option<&'static str>,
 }
 
 #[derive(TryClone)]
@@ -259,8 +256,7 @@ pub(crate) struct ModuleAttributeMacro {
 /// metadata.
 pub struct ItemMut<'a> {
     docs: &'a mut Docs,
-    #[cfg(feature = "doc")]
-    deprecated: &'a mut Option<Box<str>>,
+    deprecated: &'a mut Option<&'static str>,
 }
 
 impl ItemMut<'_> {
@@ -285,17 +281,8 @@ impl ItemMut<'_> {
     }
 
     /// Mark the given item as deprecated.
-    pub fn deprecated<S>(
-        self,
-        #[cfg_attr(not(feature = "doc"), allow(unused))] deprecated: S,
-    ) -> Result<Self, ContextError>
-    where
-        S: AsRef<str>,
-    {
-        #[cfg(feature = "doc")]
-        {
-            *self.deprecated = Some(deprecated.as_ref().try_into()?);
-        }
+    pub fn deprecated(self, deprecated: &'static str) -> Result<Self, ContextError> {
+        *self.deprecated = Some(deprecated);
 
         Ok(self)
     }
@@ -323,8 +310,7 @@ impl fmt::Debug for ItemMut<'_> {
 /// * [`Module::function_meta`].
 pub struct ItemFnMut<'a> {
     docs: &'a mut Docs,
-    #[cfg(feature = "doc")]
-    deprecated: &'a mut Option<Box<str>>,
+    deprecated: &'a mut Option<&'static str>,
     #[cfg(feature = "doc")]
     is_async: &'a mut bool,
     #[cfg(feature = "doc")]
@@ -359,17 +345,8 @@ impl ItemFnMut<'_> {
     }
 
     /// Mark the given item as deprecated.
-    pub fn deprecated<S>(
-        self,
-        #[cfg_attr(not(feature = "doc"), allow(unused))] deprecated: S,
-    ) -> Result<Self, ContextError>
-    where
-        S: AsRef<str>,
-    {
-        #[cfg(feature = "doc")]
-        {
-            *self.deprecated = Some(deprecated.as_ref().try_into()?);
-        }
+    pub fn deprecated(self, deprecated: &'static str) -> Result<Self, ContextError> {
+        *self.deprecated = Some(deprecated);
 
         Ok(self)
     }
@@ -591,17 +568,8 @@ where
     }
 
     /// Mark the given type as deprecated.
-    pub fn deprecated<S>(
-        self,
-        #[cfg_attr(not(feature = "doc"), allow(unused))] deprecated: S,
-    ) -> Result<Self, ContextError>
-    where
-        S: AsRef<str>,
-    {
-        #[cfg(feature = "doc")]
-        {
-            self.common.deprecated = Some(deprecated.as_ref().try_into()?);
-        }
+    pub fn deprecated(self, deprecated: &'static str) -> Result<Self, ContextError> {
+        self.common.deprecated = Some(deprecated);
 
         Ok(self)
     }
@@ -637,11 +605,12 @@ where
     T: ?Sized + TypeOf,
 {
     docs: &'a mut Docs,
-    #[cfg(feature = "doc")]
-    deprecated: &'a mut Option<Box<str>>,
+    deprecated: &'a mut Option<&'static str>,
     spec: &'a mut Option<TypeSpecification>,
     constructor: &'a mut Option<Arc<FunctionHandler>>,
     item: &'a Item,
+    names: &'a mut HashSet<Name>,
+    associated: &'a mut Vec<ModuleAssociated>,
     _marker: PhantomData<T>,
 }
 
@@ -670,17 +639,8 @@ where
     }
 
     /// Mark the given type as deprecated.
-    pub fn deprecated<S>(
-        self,
-        #[cfg_attr(not(feature = "doc"), allow(unused))] deprecated: S,
-    ) -> Result<Self, ContextError>
-    where
-        S: AsRef<str>,
-    {
-        #[cfg(feature = "doc")]
-        {
-            *self.deprecated = Some(deprecated.as_ref().try_into()?);
-        }
+    pub fn deprecated(self, deprecated: &'static str) -> Result<Self, ContextError> {
+        *self.deprecated = Some(deprecated);
 
         Ok(self)
     }
@@ -745,6 +705,53 @@ where
         Ok(self)
     }
 
+    /// Register comparison and equality for the current type from its [`Ord`]
+    /// implementation.
+    ///
+    /// This registers the [`Protocol::CMP`], [`Protocol::PARTIAL_CMP`],
+    /// [`Protocol::EQ`] and [`Protocol::PARTIAL_EQ`] protocols, which allows
+    /// values of the type to be compared and sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Any, Module};
+    ///
+    /// #[derive(Any, PartialEq, Eq, PartialOrd, Ord)]
+    /// struct Version(i64);
+    ///
+    /// let mut m = Module::new();
+    /// m.ty::<Version>()?.with_ord()?;
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn with_ord(mut self) -> Result<Self, ContextError>
+    where
+        T: 'static + MaybeTypeOf + UnsafeToRef + Ord,
+    {
+        self.protocol_function(Protocol::PARTIAL_EQ, |a: &T, b: &T| a == b)?;
+        self.protocol_function(Protocol::EQ, |a: &T, b: &T| a == b)?;
+        self.protocol_function(Protocol::PARTIAL_CMP, |a: &T, b: &T| a.partial_cmp(b))?;
+        self.protocol_function(Protocol::CMP, |a: &T, b: &T| a.cmp(b))?;
+        Ok(self)
+    }
+
+    fn protocol_function<F, A>(&mut self, protocol: Protocol, f: F) -> Result<(), ContextError>
+    where
+        F: InstanceFunction<A, Plain>,
+        F::Return: MaybeTypeOf,
+        A: FunctionArgs,
+    {
+        module::insert_associated_function(
+            self.names,
+            self.associated,
+            AssociatedFunctionData::from_instance_function(protocol.to_instance()?, f)?,
+            Docs::EMPTY,
+            None,
+        )?;
+
+        Ok(())
+    }
+
     fn make_struct(self, fields: Fields) -> Result<Self, ContextError> {
         let old = self.spec.replace(TypeSpecification::Struct(fields));
 
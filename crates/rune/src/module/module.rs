@@ -4,7 +4,7 @@ use ::rust_alloc::sync::Arc;
 
 use crate as rune;
 use crate::alloc::prelude::*;
-use crate::alloc::{self, Box, HashMap, HashSet, String, Vec};
+use crate::alloc::{self, HashMap, HashSet, String, Vec};
 use crate::compile::{self, meta, ContextError, Docs, IntoComponent, ItemBuf, Named};
 use crate::macros::{MacroContext, TokenStream};
 use crate::module::function_meta::{
@@ -380,7 +380,7 @@ pub struct ModuleMetaData {
 pub type ModuleMeta = fn() -> alloc::Result<ModuleMetaData>;
 
 #[derive(Debug, TryClone, PartialEq, Eq, Hash)]
-enum Name {
+pub(crate) enum Name {
     /// An associated key.
     Associated(AssociatedKey),
     /// A regular item.
@@ -472,7 +472,6 @@ impl Module {
             types_hash: HashMap::new(),
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
-                #[cfg(feature = "doc")]
                 deprecated: None,
             },
         }
@@ -482,7 +481,6 @@ impl Module {
     pub fn item_mut(&mut self) -> ItemMut<'_> {
         ItemMut {
             docs: &mut self.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut self.common.deprecated,
         }
     }
@@ -552,7 +550,6 @@ impl Module {
             item,
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
-                #[cfg(feature = "doc")]
                 deprecated: None,
             },
             hash,
@@ -568,11 +565,12 @@ impl Module {
 
         Ok(TypeMut {
             docs: &mut ty.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut ty.common.deprecated,
             spec: &mut ty.spec,
             constructor: &mut ty.constructor,
             item: &ty.item,
+            names: &mut self.names,
+            associated: &mut self.associated,
             _marker: PhantomData,
         })
     }
@@ -595,11 +593,12 @@ impl Module {
 
         Ok(TypeMut {
             docs: &mut ty.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut ty.common.deprecated,
             spec: &mut ty.spec,
             constructor: &mut ty.constructor,
             item: &ty.item,
+            names: &mut self.names,
+            associated: &mut self.associated,
             _marker: PhantomData,
         })
     }
@@ -881,7 +880,6 @@ impl Module {
             item,
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
-                #[cfg(feature = "doc")]
                 deprecated: None,
             },
             kind: ModuleItemKind::Constant(value),
@@ -891,7 +889,6 @@ impl Module {
 
         Ok(ItemMut {
             docs: &mut c.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut c.common.deprecated,
         })
     }
@@ -922,7 +919,6 @@ impl Module {
             name: associated.name,
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
-                #[cfg(feature = "doc")]
                 deprecated: None,
             },
             kind: ModuleAssociatedKind::Constant(value),
@@ -932,7 +928,6 @@ impl Module {
 
         Ok(ItemMut {
             docs: &mut last.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
         })
     }
@@ -999,7 +994,6 @@ impl Module {
                     item: data.item,
                     common: ModuleItemCommon {
                         docs,
-                        #[cfg(feature = "doc")]
                         deprecated: None,
                     },
                     kind: ModuleItemKind::Macro(ModuleMacro {
@@ -1026,7 +1020,6 @@ impl Module {
                     item: data.item,
                     common: ModuleItemCommon {
                         docs,
-                        #[cfg(feature = "doc")]
                         deprecated: None,
                     },
                     kind: ModuleItemKind::AttributeMacro(ModuleAttributeMacro {
@@ -1040,7 +1033,6 @@ impl Module {
 
         Ok(ItemMut {
             docs: &mut item.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut item.common.deprecated,
         })
     }
@@ -1100,7 +1092,6 @@ impl Module {
 
         Ok(ItemMut {
             docs: &mut m.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut m.common.deprecated,
         })
     }
@@ -1158,7 +1149,6 @@ impl Module {
             item,
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
-                #[cfg(feature = "doc")]
                 deprecated: None,
             },
             kind: ModuleItemKind::AttributeMacro(ModuleAttributeMacro { handler }),
@@ -1168,7 +1158,6 @@ impl Module {
 
         Ok(ItemMut {
             docs: &mut m.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut m.common.deprecated,
         })
     }
@@ -1253,15 +1242,13 @@ impl Module {
                 let mut docs = Docs::EMPTY;
                 docs.set_docs(meta.docs)?;
                 docs.set_arguments(meta.arguments)?;
-                let deprecated = meta.deprecated.map(TryInto::try_into).transpose()?;
-                self.function_inner(data, docs, deprecated)
+                self.function_inner(data, docs, meta.deprecated)
             }
             FunctionMetaKind::AssociatedFunction(data) => {
                 let mut docs = Docs::EMPTY;
                 docs.set_docs(meta.docs)?;
                 docs.set_arguments(meta.arguments)?;
-                let deprecated = meta.deprecated.map(TryInto::try_into).transpose()?;
-                self.insert_associated_function(data, docs, deprecated)
+                self.insert_associated_function(data, docs, meta.deprecated)
             }
         }
     }
@@ -1475,6 +1462,13 @@ impl Module {
     /// }
     /// ```
     ///
+    /// Protocol functions don't need any additional metadata to be documented.
+    /// Generated documentation describes them using the expression they back,
+    /// so a function registered for [`PARTIAL_EQ`] is shown as implementing
+    /// `==`.
+    ///
+    /// [`PARTIAL_EQ`]: crate::runtime::Protocol::PARTIAL_EQ
+    ///
     /// # Examples
     ///
     /// ```
@@ -1728,7 +1722,7 @@ impl Module {
         &mut self,
         data: FunctionData,
         docs: Docs,
-        #[allow(unused)] deprecated: Option<Box<str>>,
+        deprecated: Option<&'static str>,
     ) -> Result<ItemFnMut<'_>, ContextError> {
         let hash = Hash::type_hash(&data.item);
 
@@ -1741,11 +1735,7 @@ impl Module {
 
         self.items.try_push(ModuleItem {
             item: data.item,
-            common: ModuleItemCommon {
-                docs,
-                #[cfg(feature = "doc")]
-                deprecated,
-            },
+            common: ModuleItemCommon { docs, deprecated },
             kind: ModuleItemKind::Function(ModuleFunction {
                 handler: data.handler,
                 #[cfg(feature = "doc")]
@@ -1769,7 +1759,6 @@ impl Module {
 
         Ok(ItemFnMut {
             docs: &mut last.common.docs,
-            #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
             #[cfg(feature = "doc")]
             is_async: &mut last_fn.is_async,
@@ -1787,90 +1776,108 @@ impl Module {
         &mut self,
         data: AssociatedFunctionData,
         docs: Docs,
-        #[allow(unused)] deprecated: Option<Box<str>>,
+        deprecated: Option<&'static str>,
     ) -> Result<ItemFnMut<'_>, ContextError> {
-        self.insert_associated_name(&data.associated)?;
-
-        self.associated.try_push(ModuleAssociated {
-            container: data.associated.container,
-            container_type_info: data.associated.container_type_info,
-            name: data.associated.name,
-            common: ModuleItemCommon {
-                docs,
-                #[cfg(feature = "doc")]
-                deprecated,
-            },
-            kind: ModuleAssociatedKind::Function(ModuleFunction {
-                handler: data.handler,
-                #[cfg(feature = "doc")]
-                is_async: data.is_async,
-                #[cfg(feature = "doc")]
-                args: data.args,
-                #[cfg(feature = "doc")]
-                return_type: data.return_type,
-                #[cfg(feature = "doc")]
-                argument_types: data.argument_types,
-            }),
-        })?;
-
-        let last = self.associated.last_mut().unwrap();
+        insert_associated_function(
+            &mut self.names,
+            &mut self.associated,
+            data,
+            docs,
+            deprecated,
+        )
+    }
 
-        #[cfg(feature = "doc")]
-        let last_fn = match &mut last.kind {
-            ModuleAssociatedKind::Function(f) => f,
-            _ => unreachable!(),
-        };
+    fn insert_associated_name(&mut self, associated: &Associated) -> Result<(), ContextError> {
+        insert_associated_name(&mut self.names, associated)
+    }
+}
 
-        Ok(ItemFnMut {
-            docs: &mut last.common.docs,
-            #[cfg(feature = "doc")]
-            deprecated: &mut last.common.deprecated,
+/// Insert an associated function into the given collection of associated
+/// items.
+///
+/// This is separate from [`Module`] so that it can be used by handles which
+/// only borrow parts of it, like [`TypeMut`].
+pub(crate) fn insert_associated_function<'a>(
+    names: &mut HashSet<Name>,
+    associated: &'a mut Vec<ModuleAssociated>,
+    data: AssociatedFunctionData,
+    docs: Docs,
+    deprecated: Option<&'static str>,
+) -> Result<ItemFnMut<'a>, ContextError> {
+    insert_associated_name(names, &data.associated)?;
+
+    associated.try_push(ModuleAssociated {
+        container: data.associated.container,
+        container_type_info: data.associated.container_type_info,
+        name: data.associated.name,
+        common: ModuleItemCommon { docs, deprecated },
+        kind: ModuleAssociatedKind::Function(ModuleFunction {
+            handler: data.handler,
             #[cfg(feature = "doc")]
-            is_async: &mut last_fn.is_async,
+            is_async: data.is_async,
             #[cfg(feature = "doc")]
-            args: &mut last_fn.args,
+            args: data.args,
             #[cfg(feature = "doc")]
-            return_type: &mut last_fn.return_type,
+            return_type: data.return_type,
             #[cfg(feature = "doc")]
-            argument_types: &mut last_fn.argument_types,
-        })
-    }
+            argument_types: data.argument_types,
+        }),
+    })?;
 
-    fn insert_associated_name(&mut self, associated: &Associated) -> Result<(), ContextError> {
-        if !self
-            .names
-            .try_insert(Name::Associated(associated.as_key()?))?
-        {
-            return Err(match &associated.name.kind {
-                meta::AssociatedKind::Protocol(protocol) => {
-                    ContextError::ConflictingProtocolFunction {
-                        type_info: associated.container_type_info.try_clone()?,
-                        name: protocol.name.try_into()?,
-                    }
-                }
-                meta::AssociatedKind::FieldFn(protocol, field) => {
-                    ContextError::ConflictingFieldFunction {
-                        type_info: associated.container_type_info.try_clone()?,
-                        name: protocol.name.try_into()?,
-                        field: field.as_ref().try_into()?,
-                    }
-                }
-                meta::AssociatedKind::IndexFn(protocol, index) => {
-                    ContextError::ConflictingIndexFunction {
-                        type_info: associated.container_type_info.try_clone()?,
-                        name: protocol.name.try_into()?,
-                        index: *index,
-                    }
+    let last = associated.last_mut().unwrap();
+
+    #[cfg(feature = "doc")]
+    let last_fn = match &mut last.kind {
+        ModuleAssociatedKind::Function(f) => f,
+        _ => unreachable!(),
+    };
+
+    Ok(ItemFnMut {
+        docs: &mut last.common.docs,
+        deprecated: &mut last.common.deprecated,
+        #[cfg(feature = "doc")]
+        is_async: &mut last_fn.is_async,
+        #[cfg(feature = "doc")]
+        args: &mut last_fn.args,
+        #[cfg(feature = "doc")]
+        return_type: &mut last_fn.return_type,
+        #[cfg(feature = "doc")]
+        argument_types: &mut last_fn.argument_types,
+    })
+}
+
+fn insert_associated_name(
+    names: &mut HashSet<Name>,
+    associated: &Associated,
+) -> Result<(), ContextError> {
+    if !names.try_insert(Name::Associated(associated.as_key()?))? {
+        return Err(match &associated.name.kind {
+            meta::AssociatedKind::Protocol(protocol) => ContextError::ConflictingProtocolFunction {
+                type_info: associated.container_type_info.try_clone()?,
+                name: protocol.name.try_into()?,
+            },
+            meta::AssociatedKind::FieldFn(protocol, field) => {
+                ContextError::ConflictingFieldFunction {
+                    type_info: associated.container_type_info.try_clone()?,
+                    name: protocol.name.try_into()?,
+                    field: field.as_ref().try_into()?,
                 }
-                meta::AssociatedKind::Instance(name) => ContextError::ConflictingInstanceFunction {
+            }
+            meta::AssociatedKind::IndexFn(protocol, index) => {
+                ContextError::ConflictingIndexFunction {
                     type_info: associated.container_type_info.try_clone()?,
-                    name: name.as_ref().try_into()?,
-                },
-            });
-        }
-
-        Ok(())
+                    name: protocol.name.try_into()?,
+                    index: *index,
+                }
+            }
+            meta::AssociatedKind::Instance(name) => ContextError::ConflictingInstanceFunction {
+                type_info: associated.container_type_info.try_clone()?,
+                name: name.as_ref().try_into()?,
+            },
+        });
     }
+
+    Ok(())
 }
 
 impl AsRef<Module> for Module {
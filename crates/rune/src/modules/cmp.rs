@@ -3,9 +3,14 @@
 use core::cmp::Ordering;
 
 use crate as rune;
+use crate::alloc;
 use crate::alloc::fmt::TryWrite;
-use crate::runtime::{Formatter, Value, VmResult};
-use crate::{ContextError, Module};
+use crate::alloc::prelude::*;
+use crate::runtime::{
+    EnvProtocolCaller, Formatter, FromValue, Function, Hasher, Protocol, Stack, TypeOf, Value, Vec,
+    VmErrorKind, VmResult,
+};
+use crate::{Any, ContextError, Hash, Module};
 
 /// Construct the `std::cmp` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -52,12 +57,283 @@ pub fn module() -> Result<Module, ContextError> {
 
     m.function_meta(ordering_partial_eq)?;
     m.function_meta(ordering_eq)?;
+    m.function_meta(ordering_hash)?;
     m.function_meta(ordering_string_debug)?;
+    m.function_meta(ordering_string_display)?;
+    m.function_meta(reverse)?;
+    m.function_meta(ordering_neg)?;
+    m.function_meta(then)?;
+    m.function_meta(then_with)?;
+    m.function_meta(then_cmp)?;
+    m.function_meta(is_eq)?;
+    m.function_meta(is_ne)?;
+    m.function_meta(is_lt)?;
+    m.function_meta(is_gt)?;
+    m.function_meta(is_le)?;
+    m.function_meta(is_ge)?;
+    m.function_meta(from_int)?;
+    m.function_meta(parse)?;
+    m.function_meta(as_int)?;
+    m.function_meta(all)?;
+    m.ty::<Reverse>()?.constructor(Reverse)?;
+
+    m.function_meta(partial_cmp)?;
+    m.function_meta(partial_then)?;
+    m.function_meta(compare)?;
+    m.function_meta(dir)?;
+    m.function_meta(rank)?;
+
+    m.raw_function("choose", choose)
+        .build()?
+        .args(4)
+        .argument_types([Some(Ordering::type_of()), None, None, None])?
+        .docs([
+            "Choose one of three values depending on the given `ordering`.",
+            "",
+            "Returns `less` for `Ordering::Less`, `equal` for `Ordering::Equal` and",
+            "`greater` for `Ordering::Greater`.",
+            "",
+            "# Examples",
+            "",
+            "```rune",
+            "use std::cmp::{choose, Ordering};",
+            "use std::ops::cmp;",
+            "",
+            "assert_eq!(choose(Ordering::Less, \"lt\", \"eq\", \"gt\"), \"lt\");",
+            "assert_eq!(choose(Ordering::Equal, \"lt\", \"eq\", \"gt\"), \"eq\");",
+            "assert_eq!(choose(cmp(2, 1), \"lt\", \"eq\", \"gt\"), \"gt\");",
+            "```",
+        ])?;
+
     m.function_meta(min)?;
     m.function_meta(max)?;
+    m.function_meta(min_by)?;
+    m.function_meta(max_by)?;
+    m.function_meta(equal_by)?;
+    m.function_meta(min_by_key)?;
+    m.function_meta(max_by_key)?;
+    m.function_meta(minmax)?;
+    m.function_meta(clamp)?;
+    m.function_meta(clamp_all)?;
+    m.function_meta(min_of)?;
+    m.function_meta(max_of)?;
     Ok(m)
 }
 
+/// A helper struct for reverse ordering.
+///
+/// This struct is a helper to be used with functions like [`Vec::sort`] and can
+/// be used to reverse order a part of a key.
+///
+/// Internally uses the [`CMP`] protocol of the wrapped value, inverting its
+/// result.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Reverse;
+///
+/// let values = [Reverse(1), Reverse(3), Reverse(2)];
+/// values.sort();
+///
+/// let values = values.iter().map(|v| v.0).collect::<Vec>();
+/// assert_eq!(values, [3, 2, 1]);
+///
+/// let values = [Reverse(Reverse(1)), Reverse(Reverse(3)), Reverse(Reverse(2))];
+/// values.sort();
+///
+/// let values = values.iter().map(|v| (v.0).0).collect::<Vec>();
+/// assert_eq!(values, [1, 2, 3]);
+/// ```
+#[derive(Any)]
+#[rune(module = crate, item = ::std::cmp, install_with = Reverse::install)]
+struct Reverse(#[rune(get)] Value);
+
+impl Reverse {
+    /// Compare two reversed values.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::{Ordering, Reverse};
+    /// use std::ops::cmp;
+    ///
+    /// assert_eq!(cmp(Reverse(1), Reverse(2)), Ordering::Greater);
+    /// assert_eq!(cmp(Reverse(2), Reverse(2)), Ordering::Equal);
+    /// assert_eq!(cmp(Reverse(Reverse(1)), Reverse(Reverse(2))), Ordering::Less);
+    /// ```
+    #[rune::function(instance, protocol = CMP)]
+    fn cmp(&self, other: &Self) -> VmResult<Ordering> {
+        Value::cmp(&other.0, &self.0)
+    }
+
+    fn install(m: &mut Module) -> Result<(), ContextError> {
+        m.function_meta(Self::cmp)?;
+        Ok(())
+    }
+}
+
+/// Perform a partial comparison over two values.
+///
+/// Returns `None` if the values are incomparable, such as when either of them
+/// is `NaN`.
+///
+/// Internally uses the [`PARTIAL_CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::{partial_cmp, Ordering};
+///
+/// assert_eq!(partial_cmp(1.0, 2.0), Some(Ordering::Less));
+/// assert_eq!(partial_cmp(2.0, 2.0), Some(Ordering::Equal));
+/// assert_eq!(partial_cmp(f64::NAN, 1.0), None);
+/// ```
+#[rune::function]
+fn partial_cmp(v1: Value, v2: Value) -> VmResult<Option<Ordering>> {
+    Value::partial_cmp(&v1, &v2)
+}
+
+/// Chains two partial orderings.
+///
+/// Returns `prev` unchanged when it's not `Some(Ordering::Equal)`, which
+/// includes when it is `None`. Otherwise calls `next` and returns the result.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::{partial_cmp, partial_then, Ordering};
+///
+/// let x = (1.0, 2.0);
+/// let y = (1.0, 5.0);
+/// let result = partial_then(partial_cmp(x.0, y.0), || partial_cmp(x.1, y.1));
+/// assert_eq!(result, Some(Ordering::Less));
+///
+/// let result = partial_then(Some(Ordering::Greater), || panic!("should not be called"));
+/// assert_eq!(result, Some(Ordering::Greater));
+///
+/// let result = partial_then(partial_cmp(f64::NAN, 1.0), || panic!("should not be called"));
+/// assert_eq!(result, None);
+/// ```
+#[rune::function]
+fn partial_then(prev: Option<Ordering>, next: &Function) -> VmResult<Option<Ordering>> {
+    match prev {
+        Some(Ordering::Equal) => next.call(()),
+        ordering => VmResult::Ok(ordering),
+    }
+}
+
+/// Perform a lexicographic comparison over two sequences.
+///
+/// If both values are tuples or vectors their elements are compared in order
+/// using the [`CMP`] protocol, and the first ordering which is not `Equal` is
+/// returned. If all elements are equal, the shorter sequence is considered
+/// smaller. Tuples and vectors can be compared with each other.
+///
+/// Any other values are compared directly using the [`CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::{compare, Ordering};
+///
+/// assert_eq!(compare((1, 2), (1, 3)), Ordering::Less);
+/// assert_eq!(compare((1, 2), (1, 2)), Ordering::Equal);
+/// assert_eq!(compare((1, 2, 3), (1, 2)), Ordering::Greater);
+/// assert_eq!(compare((), (1,)), Ordering::Less);
+/// assert_eq!(compare((1, 2), [1, 2]), Ordering::Equal);
+/// assert_eq!(compare(2, 1), Ordering::Greater);
+/// ```
+#[rune::function]
+fn compare(v1: Value, v2: Value) -> VmResult<Ordering> {
+    let (Some(a), Some(b)) = (vm_try!(elements(&v1)), vm_try!(elements(&v2))) else {
+        return Value::cmp(&v1, &v2);
+    };
+
+    Vec::cmp_with(&a, &b, &mut EnvProtocolCaller)
+}
+
+/// Orient an ordering according to a sort direction.
+///
+/// Returns `ordering` as-is if `ascending` is `true`, and reversed otherwise.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::dir;
+/// use std::ops::cmp;
+///
+/// let values = [3, 1, 2];
+/// values.sort_by(|a, b| dir(cmp(a, b), false));
+/// assert_eq!(values, [3, 2, 1]);
+/// ```
+#[rune::function]
+fn dir(ordering: Ordering, ascending: bool) -> Ordering {
+    if ascending {
+        ordering
+    } else {
+        ordering.reverse()
+    }
+}
+
+/// Compare two values and rank the result as an integer.
+///
+/// Returns `0` if `a` is less than `b`, `1` if they are equal and `2` if `a` is
+/// greater than `b`, which is useful for indexing into arrays.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::rank;
+///
+/// let names = ["less", "equal", "greater"];
+/// assert_eq!(names[rank(1, 2)], "less");
+/// assert_eq!(names[rank(2, 2)], "equal");
+/// assert_eq!(names[rank(3, 2)], "greater");
+/// ```
+#[rune::function]
+fn rank(a: Value, b: Value) -> VmResult<i64> {
+    VmResult::Ok(match vm_try!(Value::cmp(&a, &b)) {
+        Ordering::Less => 0,
+        Ordering::Equal => 1,
+        Ordering::Greater => 2,
+    })
+}
+
+/// Copy out the elements of a tuple or a vector.
+fn elements(value: &Value) -> VmResult<Option<alloc::Vec<Value>>> {
+    let elements = match value {
+        Value::EmptyTuple => alloc::Vec::new(),
+        Value::Tuple(tuple) => vm_try!(vm_try!(tuple.borrow_ref()).iter().cloned().try_collect()),
+        Value::Vec(vec) => vm_try!(vm_try!(vec.borrow_ref()).iter().cloned().try_collect()),
+        _ => return VmResult::Ok(None),
+    };
+
+    VmResult::Ok(Some(elements))
+}
+
+/// Raw implementation of `choose`, since functions taking four arguments
+/// can't be registered through `#[rune::function]` in all configurations.
+fn choose(stack: &mut Stack, args: usize) -> VmResult<()> {
+    if args != 4 {
+        return VmResult::err(VmErrorKind::BadArgumentCount {
+            actual: args,
+            expected: 4,
+        });
+    }
+
+    let [ordering, less, equal, greater] = vm_try!(stack.drain_vec(4));
+
+    let value = match vm_try!(Ordering::from_value(ordering)) {
+        Ordering::Less => less,
+        Ordering::Equal => equal,
+        Ordering::Greater => greater,
+    };
+
+    vm_try!(stack.push(value));
+    VmResult::Ok(())
+}
+
 /// Compares and returns the maximum of two values.
 ///
 /// Returns the second argument if the comparison determines them to be equal.
@@ -74,7 +350,7 @@ pub fn module() -> Result<Module, ContextError> {
 /// ```
 #[rune::function]
 fn max(v1: Value, v2: Value) -> VmResult<Value> {
-    VmResult::Ok(match vm_try!(Value::cmp(&v1, &v2)) {
+    VmResult::Ok(match vm_try!(cmp_in("max", &v1, &v2)) {
         Ordering::Less | Ordering::Equal => v2,
         Ordering::Greater => v1,
     })
@@ -96,14 +372,356 @@ fn max(v1: Value, v2: Value) -> VmResult<Value> {
 /// ```
 #[rune::function]
 fn min(v1: Value, v2: Value) -> VmResult<Value> {
-    VmResult::Ok(match vm_try!(Value::cmp(&v1, &v2)) {
+    VmResult::Ok(match vm_try!(cmp_in("min", &v1, &v2)) {
         Ordering::Less | Ordering::Equal => v1,
         Ordering::Greater => v2,
     })
 }
 
+/// Compare two values on behalf of `function`.
+///
+/// If the values themselves can't be compared, the error names the types
+/// involved and the function the comparison was performed in.
+fn cmp_in(function: &'static str, v1: &Value, v2: &Value) -> VmResult<Ordering> {
+    let error = match Value::cmp(v1, v2) {
+        VmResult::Ok(ordering) => return VmResult::Ok(ordering),
+        VmResult::Err(error) => error,
+    };
+
+    // Only replace errors caused by the outermost comparison, errors from
+    // comparing nested values are more precise as they are.
+    let outermost = match error.at().kind() {
+        VmErrorKind::UnsupportedBinaryOperation {
+            op: "cmp",
+            lhs,
+            rhs,
+        } => *lhs == vm_try!(v1.type_info()) && *rhs == vm_try!(v2.type_info()),
+        VmErrorKind::MissingInstanceFunction { hash, .. } => {
+            *hash == Hash::associated_function(vm_try!(v1.type_hash()), Protocol::CMP.hash)
+        }
+        // The CMP protocol of the first value rejected the second argument.
+        VmErrorKind::Expected { actual, .. } => *actual == vm_try!(v2.type_info()),
+        _ => false,
+    };
+
+    if outermost {
+        return VmResult::err(VmErrorKind::UnsupportedComparison {
+            function,
+            lhs: vm_try!(v1.type_info()),
+            rhs: vm_try!(v2.type_info()),
+        });
+    }
+
+    VmResult::Err(error)
+}
+
+/// Returns the minimum of two values with respect to the specified comparison
+/// function.
+///
+/// Returns the first argument if the comparison determines them to be equal.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::min_by;
+/// use std::ops::cmp;
+///
+/// let abs_cmp = |x, y| cmp(x.abs(), y.abs());
+///
+/// assert_eq!(min_by(-2, 1, abs_cmp), 1);
+/// assert_eq!(min_by(-2, 2, abs_cmp), -2);
+/// ```
+#[rune::function]
+fn min_by(v1: Value, v2: Value, compare: &Function) -> VmResult<Value> {
+    VmResult::Ok(match vm_try!(compare.call::<_, Ordering>((&v1, &v2))) {
+        Ordering::Less | Ordering::Equal => v1,
+        Ordering::Greater => v2,
+    })
+}
+
+/// Returns the maximum of two values with respect to the specified comparison
+/// function.
+///
+/// Returns the second argument if the comparison determines them to be equal.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::max_by;
+/// use std::ops::cmp;
+///
+/// let abs_cmp = |x, y| cmp(x.abs(), y.abs());
+///
+/// assert_eq!(max_by(-2, 1, abs_cmp), -2);
+/// assert_eq!(max_by(-2, 2, abs_cmp), 2);
+/// ```
+#[rune::function]
+fn max_by(v1: Value, v2: Value, compare: &Function) -> VmResult<Value> {
+    VmResult::Ok(match vm_try!(compare.call::<_, Ordering>((&v1, &v2))) {
+        Ordering::Less | Ordering::Equal => v2,
+        Ordering::Greater => v1,
+    })
+}
+
+/// Tests two values for equality with respect to the specified equality
+/// function.
+///
+/// The function must return a boolean.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::equal_by;
+///
+/// let abs_eq = |x, y| x.abs() == y.abs();
+///
+/// assert!(equal_by(-2, 2, abs_eq));
+/// assert!(!equal_by(-2, 1, abs_eq));
+/// ```
+#[rune::function]
+fn equal_by(v1: Value, v2: Value, eq: &Function) -> VmResult<bool> {
+    eq.call::<_, bool>((&v1, &v2))
+}
+
+/// Returns the element that gives the minimum value from the specified
+/// function.
+///
+/// Returns the first argument if the comparison determines them to be equal.
+///
+/// The keys are compared using the [`CMP`] protocol, and the key function is
+/// called once for each value.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::min_by_key;
+///
+/// assert_eq!(min_by_key("hello", "hi", |s| s.len()), "hi");
+/// assert_eq!(min_by_key("ab", "cd", |s| s.len()), "ab");
+/// assert_eq!(min_by_key(-2, 1, |x| x.abs()), 1);
+/// ```
+#[rune::function]
+fn min_by_key(v1: Value, v2: Value, key: &Function) -> VmResult<Value> {
+    let k1 = vm_try!(key.call::<_, Value>((&v1,)));
+    let k2 = vm_try!(key.call::<_, Value>((&v2,)));
+
+    VmResult::Ok(match vm_try!(Value::cmp(&k1, &k2)) {
+        Ordering::Less | Ordering::Equal => v1,
+        Ordering::Greater => v2,
+    })
+}
+
+/// Returns the element that gives the maximum value from the specified
+/// function.
+///
+/// Returns the second argument if the comparison determines them to be equal.
+///
+/// The keys are compared using the [`CMP`] protocol, and the key function is
+/// called once for each value.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::max_by_key;
+///
+/// assert_eq!(max_by_key("hello", "hi", |s| s.len()), "hello");
+/// assert_eq!(max_by_key("ab", "cd", |s| s.len()), "cd");
+/// assert_eq!(max_by_key(-2, 1, |x| x.abs()), -2);
+/// ```
+#[rune::function]
+fn max_by_key(v1: Value, v2: Value, key: &Function) -> VmResult<Value> {
+    let k1 = vm_try!(key.call::<_, Value>((&v1,)));
+    let k2 = vm_try!(key.call::<_, Value>((&v2,)));
+
+    VmResult::Ok(match vm_try!(Value::cmp(&k1, &k2)) {
+        Ordering::Less | Ordering::Equal => v2,
+        Ordering::Greater => v1,
+    })
+}
+
+/// Compares and returns the minimum and maximum of two values as a tuple of
+/// `(min, max)`.
+///
+/// Returns `(v1, v2)` if the comparison determines them to be equal.
+///
+/// Internally uses the [`CMP`] protocol, and only compares the values once.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::minmax;
+///
+/// assert_eq!(minmax(1, 2), (1, 2));
+/// assert_eq!(minmax(2, 1), (1, 2));
+/// assert_eq!(minmax(2, 2), (2, 2));
+///
+/// let (min, max) = minmax(-1.5, -3.0);
+/// assert_eq!(min, -3.0);
+/// assert_eq!(max, -1.5);
+/// ```
+#[rune::function]
+fn minmax(v1: Value, v2: Value) -> VmResult<(Value, Value)> {
+    VmResult::Ok(match vm_try!(Value::cmp(&v1, &v2)) {
+        Ordering::Less | Ordering::Equal => (v1, v2),
+        Ordering::Greater => (v2, v1),
+    })
+}
+
+/// Restrict a value to a certain interval.
+///
+/// Returns `high` if `value` is greater than `high`, and `low` if `value` is
+/// less than `low`. Otherwise this returns `value`.
+///
+/// Internally uses the [`CMP`] protocol.
+///
+/// # Panics
+///
+/// Panics if `low > high`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::clamp;
+///
+/// assert_eq!(clamp(-3, -2, 1), -2);
+/// assert_eq!(clamp(0, -2, 1), 0);
+/// assert_eq!(clamp(2, -2, 1), 1);
+///
+/// assert_eq!(clamp(-3.0, -2.0, 1.0), -2.0);
+/// assert_eq!(clamp(0.0, -2.0, 1.0), 0.0);
+/// assert_eq!(clamp(2.0, -2.0, 1.0), 1.0);
+/// ```
+#[rune::function]
+fn clamp(value: Value, low: Value, high: Value) -> VmResult<Value> {
+    if vm_try!(Value::cmp(&low, &high)) == Ordering::Greater {
+        return VmResult::panic("`low` must be less than or equal to `high` in `clamp`");
+    }
+
+    clamp_with(value, &low, &high)
+}
+
+/// Restrict every value produced by an iterable to a certain interval,
+/// collecting the results into a vector in their original order.
+///
+/// See [`clamp`] for how each value is restricted.
+///
+/// Internally uses the [`CMP`] protocol.
+///
+/// # Panics
+///
+/// Panics if `low > high`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::clamp_all;
+///
+/// assert_eq!(clamp_all([-3, 0, 2], -2, 1), [-2, 0, 1]);
+/// assert_eq!(clamp_all((1..=5).iter(), 2, 4), [2, 2, 3, 4, 4]);
+/// assert_eq!(clamp_all([], 0, 1), []);
+/// ```
+#[rune::function]
+fn clamp_all(values: Value, low: Value, high: Value) -> VmResult<Vec> {
+    if vm_try!(Value::cmp(&low, &high)) == Ordering::Greater {
+        return VmResult::panic("`low` must be less than or equal to `high` in `clamp_all`");
+    }
+
+    let mut it = vm_try!(values.into_iter());
+    let mut output = Vec::new();
+
+    while let Some(value) = vm_try!(it.next()) {
+        vm_try!(output.push_value(vm_try!(clamp_with(value, &low, &high))));
+    }
+
+    VmResult::Ok(output)
+}
+
+/// Returns the minimum of the values produced by an iterable, or `None` if it
+/// is empty.
+///
+/// Returns the first of the minimum values if several compare equal, like
+/// [`min`].
+///
+/// Internally uses the [`CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::min_of;
+///
+/// assert_eq!(min_of([3, 1, 2]), Some(1));
+/// assert_eq!(min_of((1..=5).iter()), Some(1));
+/// assert_eq!(min_of([]), None);
+/// ```
+#[rune::function]
+fn min_of(values: Value) -> VmResult<Option<Value>> {
+    let mut it = vm_try!(values.into_iter());
+
+    let Some(mut min) = vm_try!(it.next()) else {
+        return VmResult::Ok(None);
+    };
+
+    while let Some(value) = vm_try!(it.next()) {
+        if vm_try!(cmp_in("min_of", &min, &value)) == Ordering::Greater {
+            min = value;
+        }
+    }
+
+    VmResult::Ok(Some(min))
+}
+
+/// Returns the maximum of the values produced by an iterable, or `None` if it
+/// is empty.
+///
+/// Returns the last of the maximum values if several compare equal, like
+/// [`max`].
+///
+/// Internally uses the [`CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::max_of;
+///
+/// assert_eq!(max_of([3, 1, 2]), Some(3));
+/// assert_eq!(max_of((1..=5).iter()), Some(5));
+/// assert_eq!(max_of([]), None);
+/// ```
+#[rune::function]
+fn max_of(values: Value) -> VmResult<Option<Value>> {
+    let mut it = vm_try!(values.into_iter());
+
+    let Some(mut max) = vm_try!(it.next()) else {
+        return VmResult::Ok(None);
+    };
+
+    while let Some(value) = vm_try!(it.next()) {
+        if vm_try!(cmp_in("max_of", &max, &value)) != Ordering::Greater {
+            max = value;
+        }
+    }
+
+    VmResult::Ok(Some(max))
+}
+
+/// Restrict a value to an interval which is known to be valid.
+fn clamp_with(value: Value, low: &Value, high: &Value) -> VmResult<Value> {
+    if vm_try!(Value::cmp(&value, low)) == Ordering::Less {
+        return VmResult::Ok(low.clone());
+    }
+
+    if vm_try!(Value::cmp(&value, high)) == Ordering::Greater {
+        return VmResult::Ok(high.clone());
+    }
+
+    VmResult::Ok(value)
+}
+
 /// Perform a partial ordering equality test.
 ///
+/// To compare an ordering against an integer, first convert it with
+/// [`Ordering::as_int`].
+///
 /// # Examples
 ///
 /// ```rune
@@ -111,6 +729,7 @@ fn min(v1: Value, v2: Value) -> VmResult<Value> {
 ///
 /// assert!(Ordering::Less == Ordering::Less);
 /// assert!(Ordering::Less != Ordering::Equal);
+/// assert!(Ordering::Less.as_int() == -1);
 /// ```
 #[rune::function(instance, protocol = PARTIAL_EQ)]
 fn ordering_partial_eq(this: Ordering, other: Ordering) -> bool {
@@ -133,6 +752,344 @@ fn ordering_eq(this: Ordering, other: Ordering) -> bool {
     this == other
 }
 
+/// Calculate a hash for an [`Ordering`].
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::hash;
+///
+/// assert_eq!(hash(Ordering::Less), hash(Ordering::Less));
+/// assert_ne!(hash(Ordering::Less), hash(Ordering::Greater));
+/// ```
+#[rune::function(instance, protocol = HASH)]
+fn ordering_hash(this: Ordering, hasher: &mut Hasher) {
+    hasher.write_i64(this as i64);
+}
+
+/// Reverses the `Ordering`.
+///
+/// * `Less` becomes `Greater`.
+/// * `Greater` becomes `Less`.
+/// * `Equal` becomes `Equal`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.reverse(), Ordering::Greater);
+/// assert_eq!(Ordering::Equal.reverse(), Ordering::Equal);
+/// assert_eq!(Ordering::Greater.reverse(), Ordering::Less);
+/// ```
+#[rune::function(instance)]
+fn reverse(this: Ordering) -> Ordering {
+    this.reverse()
+}
+
+/// Reverse the `Ordering` using the unary `-` operator.
+///
+/// This is the same as [`Ordering::reverse`].
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::cmp;
+///
+/// assert_eq!(-Ordering::Less, Ordering::Greater);
+/// assert_eq!(-Ordering::Equal, Ordering::Equal);
+/// assert_eq!(-cmp(1, 2), Ordering::Greater);
+/// ```
+#[rune::function(instance, protocol = NEG)]
+fn ordering_neg(this: Ordering) -> Ordering {
+    this.reverse()
+}
+
+/// Chains two orderings.
+///
+/// Returns `self` when it's not `Equal`. Otherwise returns `other`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::cmp;
+///
+/// let result = Ordering::Equal.then(Ordering::Less);
+/// assert_eq!(result, Ordering::Less);
+///
+/// let result = Ordering::Less.then(Ordering::Equal);
+/// assert_eq!(result, Ordering::Less);
+///
+/// let result = Ordering::Less.then(Ordering::Greater);
+/// assert_eq!(result, Ordering::Less);
+///
+/// let result = Ordering::Equal.then(Ordering::Equal);
+/// assert_eq!(result, Ordering::Equal);
+///
+/// let x = (1, 2, 7);
+/// let y = (1, 5, 3);
+/// let result = cmp(x.0, y.0).then(cmp(x.1, y.1)).then(cmp(x.2, y.2));
+///
+/// assert_eq!(result, Ordering::Less);
+/// ```
+#[rune::function(instance)]
+fn then(this: Ordering, other: Ordering) -> Ordering {
+    this.then(other)
+}
+
+/// Chains the ordering with the given function.
+///
+/// Returns `self` when it's not `Equal`. Otherwise calls `f` and returns the
+/// result.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::cmp;
+///
+/// let result = Ordering::Equal.then_with(|| Ordering::Less);
+/// assert_eq!(result, Ordering::Less);
+///
+/// let result = Ordering::Less.then_with(|| Ordering::Equal);
+/// assert_eq!(result, Ordering::Less);
+///
+/// let result = Ordering::Less.then_with(|| panic!("should not be called"));
+/// assert_eq!(result, Ordering::Less);
+///
+/// let x = (1, 2, 7);
+/// let y = (1, 5, 3);
+/// let result = cmp(x.0, y.0).then_with(|| cmp(x.1, y.1)).then_with(|| cmp(x.2, y.2));
+///
+/// assert_eq!(result, Ordering::Less);
+/// ```
+#[rune::function(instance)]
+fn then_with(this: Ordering, other: Function) -> VmResult<Ordering> {
+    match this {
+        Ordering::Equal => other.call(()),
+        ordering => VmResult::Ok(ordering),
+    }
+}
+
+/// Chains the ordering with a comparison of `a` and `b`.
+///
+/// Returns `self` when it's not `Equal`. Otherwise `a` and `b` are compared
+/// using the [`CMP`] protocol, which only happens when needed.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::cmp;
+///
+/// assert_eq!(Ordering::Equal.then_cmp(1, 2), Ordering::Less);
+/// assert_eq!(Ordering::Greater.then_cmp(1, 2), Ordering::Greater);
+///
+/// let x = (1, 2, 7);
+/// let y = (1, 5, 3);
+/// let result = cmp(x.0, y.0).then_cmp(x.1, y.1).then_cmp(x.2, y.2);
+///
+/// assert_eq!(result, Ordering::Less);
+/// ```
+#[rune::function(instance)]
+fn then_cmp(this: Ordering, a: Value, b: Value) -> VmResult<Ordering> {
+    match this {
+        Ordering::Equal => Value::cmp(&a, &b),
+        ordering => VmResult::Ok(ordering),
+    }
+}
+
+/// Returns `true` if the ordering is the `Equal` variant.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.is_eq(), false);
+/// assert_eq!(Ordering::Equal.is_eq(), true);
+/// assert_eq!(Ordering::Greater.is_eq(), false);
+/// ```
+#[rune::function(instance)]
+fn is_eq(this: Ordering) -> bool {
+    this.is_eq()
+}
+
+/// Returns `true` if the ordering is not the `Equal` variant.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.is_ne(), true);
+/// assert_eq!(Ordering::Equal.is_ne(), false);
+/// assert_eq!(Ordering::Greater.is_ne(), true);
+/// ```
+#[rune::function(instance)]
+fn is_ne(this: Ordering) -> bool {
+    this.is_ne()
+}
+
+/// Returns `true` if the ordering is the `Less` variant.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.is_lt(), true);
+/// assert_eq!(Ordering::Equal.is_lt(), false);
+/// assert_eq!(Ordering::Greater.is_lt(), false);
+/// ```
+#[rune::function(instance)]
+fn is_lt(this: Ordering) -> bool {
+    this.is_lt()
+}
+
+/// Returns `true` if the ordering is the `Greater` variant.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.is_gt(), false);
+/// assert_eq!(Ordering::Equal.is_gt(), false);
+/// assert_eq!(Ordering::Greater.is_gt(), true);
+/// ```
+#[rune::function(instance)]
+fn is_gt(this: Ordering) -> bool {
+    this.is_gt()
+}
+
+/// Returns `true` if the ordering is either the `Less` or `Equal` variant.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.is_le(), true);
+/// assert_eq!(Ordering::Equal.is_le(), true);
+/// assert_eq!(Ordering::Greater.is_le(), false);
+/// ```
+#[rune::function(instance)]
+fn is_le(this: Ordering) -> bool {
+    this.is_le()
+}
+
+/// Returns `true` if the ordering is either the `Greater` or `Equal` variant.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.is_ge(), false);
+/// assert_eq!(Ordering::Equal.is_ge(), true);
+/// assert_eq!(Ordering::Greater.is_ge(), true);
+/// ```
+#[rune::function(instance)]
+fn is_ge(this: Ordering) -> bool {
+    this.is_ge()
+}
+
+/// Construct an ordering from an integer.
+///
+/// Negative integers produce `Less`, zero produces `Equal` and positive
+/// integers produce `Greater`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::from_int(-10), Ordering::Less);
+/// assert_eq!(Ordering::from_int(0), Ordering::Equal);
+/// assert_eq!(Ordering::from_int(3), Ordering::Greater);
+/// ```
+#[rune::function(free, path = Ordering::from_int)]
+fn from_int(value: i64) -> Ordering {
+    value.cmp(&0)
+}
+
+/// Parse an ordering from a string.
+///
+/// Accepts `"less"`, `"equal"` and `"greater"`, ignoring ASCII case. Any
+/// other string produces an error.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::parse("less"), Ok(Ordering::Less));
+/// assert_eq!(Ordering::parse("Equal"), Ok(Ordering::Equal));
+/// assert_eq!(Ordering::parse("GREATER"), Ok(Ordering::Greater));
+/// assert!(Ordering::parse("sideways").is_err());
+/// ```
+#[rune::function(free, path = Ordering::parse)]
+fn parse(s: &str) -> VmResult<Result<Ordering, alloc::String>> {
+    for (name, ordering) in [
+        ("less", Ordering::Less),
+        ("equal", Ordering::Equal),
+        ("greater", Ordering::Greater),
+    ] {
+        if s.eq_ignore_ascii_case(name) {
+            return VmResult::Ok(Ok(ordering));
+        }
+    }
+
+    let message = vm_try!(alloc::fmt::try_format(format_args!(
+        "Unknown ordering `{s}`, expected one of `less`, `equal` or `greater`"
+    )));
+
+    VmResult::Ok(Err(message))
+}
+
+/// Convert the ordering into an integer.
+///
+/// Returns `-1` for `Less`, `0` for `Equal` and `1` for `Greater`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less.as_int(), -1);
+/// assert_eq!(Ordering::Equal.as_int(), 0);
+/// assert_eq!(Ordering::Greater.as_int(), 1);
+/// ```
+#[rune::function(instance)]
+fn as_int(this: Ordering) -> i64 {
+    this as i64
+}
+
+/// Get all orderings, from `Less` to `Greater`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// let all = Ordering::all();
+/// assert_eq!(all, [Ordering::Less, Ordering::Equal, Ordering::Greater]);
+/// ```
+#[rune::function(free, path = Ordering::all)]
+fn all() -> VmResult<Vec> {
+    let mut vec = vm_try!(Vec::with_capacity(3));
+
+    for ordering in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+        vm_try!(vec.push_value(ordering));
+    }
+
+    VmResult::Ok(vec)
+}
+
 /// Debug format [`Ordering`].
 ///
 /// # Examples
@@ -147,3 +1104,20 @@ fn ordering_string_debug(this: Ordering, s: &mut Formatter) -> VmResult<()> {
     vm_write!(s, "{:?}", this);
     VmResult::Ok(())
 }
+
+/// Display format [`Ordering`].
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+/// use std::ops::cmp;
+///
+/// assert_eq!(format!("{}", Ordering::Less), "Less");
+/// assert_eq!(format!("{}", cmp(2, 1)), "Greater");
+/// ```
+#[rune::function(instance, protocol = STRING_DISPLAY)]
+fn ordering_string_display(this: Ordering, s: &mut Formatter) -> VmResult<()> {
+    vm_write!(s, "{:?}", this);
+    VmResult::Ok(())
+}
@@ -31,6 +31,7 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(eq)?;
     m.function_meta(partial_cmp)?;
     m.function_meta(cmp)?;
+    m.function_meta(total_cmp)?;
 
     m.constant("EPSILON", f64::EPSILON).build()?;
     m.constant("MIN", f64::MIN).build()?;
@@ -352,3 +353,27 @@ fn cmp(this: f64, rhs: f64) -> VmResult<Ordering> {
 
     VmResult::Ok(ordering)
 }
+
+/// Return the ordering between `self` and `other`.
+///
+/// Unlike the standard partial comparison between floating point numbers, this
+/// comparison always produces an ordering in accordance to the `totalOrder`
+/// predicate as defined in the IEEE 754 (2008 revision) floating point
+/// standard. Negative quiet `NaN` is ordered before negative infinity, `-0.0`
+/// before `0.0`, and positive `NaN` after positive infinity.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(5.0.total_cmp(10.0), Ordering::Less);
+/// assert_eq!((-0.0).total_cmp(0.0), Ordering::Less);
+/// assert_eq!(f64::NAN.total_cmp(f64::INFINITY), Ordering::Greater);
+/// assert_eq!(f64::NAN.total_cmp(f64::NAN), Ordering::Equal);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn total_cmp(this: f64, rhs: f64) -> Ordering {
+    this.total_cmp(&rhs)
+}
@@ -26,12 +26,14 @@ pub fn module() -> Result<Module, ContextError> {
     module.function_meta(checked_div)?;
     module.function_meta(checked_mul)?;
     module.function_meta(checked_rem)?;
+    module.function_meta(checked_neg)?;
 
     module.function_meta(wrapping_add)?;
     module.function_meta(wrapping_sub)?;
     module.function_meta(wrapping_div)?;
     module.function_meta(wrapping_mul)?;
     module.function_meta(wrapping_rem)?;
+    module.function_meta(wrapping_neg)?;
 
     module.function_meta(saturating_add)?;
     module.function_meta(saturating_sub)?;
@@ -264,6 +266,24 @@ fn checked_rem(this: i64, rhs: i64) -> Option<i64> {
     i64::checked_rem(this, rhs)
 }
 
+/// Checked negation. Computes `-self`, returning `None` if `self == MIN`.
+///
+/// Note that the `-` operator raises an overflow error in that case instead.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(5.checked_neg(), Some(-5));
+/// assert_eq!(i64::MIN.checked_neg(), None);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn checked_neg(this: i64) -> Option<i64> {
+    i64::checked_neg(this)
+}
+
 /// Wrapping (modular) addition. Computes `self + rhs`, wrapping around at the
 /// boundary of the type.
 ///
@@ -289,8 +309,8 @@ fn wrapping_add(this: i64, rhs: i64) -> i64 {
 /// Basic usage:
 ///
 /// ```rune
-// assert_eq!(0.wrapping_sub(127), -127);
-// assert_eq!((-2).wrapping_sub(i64::MAX), i64::MAX);
+/// assert_eq!(0.wrapping_sub(127), -127);
+/// assert_eq!((-2).wrapping_sub(i64::MAX), i64::MAX);
 /// ```
 #[rune::function(instance)]
 #[inline]
@@ -371,6 +391,30 @@ fn wrapping_rem(this: i64, rhs: i64) -> VmResult<i64> {
     VmResult::Ok(i64::wrapping_rem(this, rhs))
 }
 
+/// Wrapping (modular) negation. Computes `-self`, wrapping around at the
+/// boundary of the type.
+///
+/// The only case where such wrapping can occur is when one negates `MIN` on a
+/// signed type (where `MIN` is the negative minimal value for the type); this
+/// is a positive value that is too large to represent in the type. In such a
+/// case, this function returns `MIN` itself.
+///
+/// Like the other wrapping operations, this never errors or panics.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// assert_eq!(100.wrapping_neg(), -100);
+/// assert_eq!(i64::MIN.wrapping_neg(), i64::MIN);
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn wrapping_neg(this: i64) -> i64 {
+    i64::wrapping_neg(this)
+}
+
 /// Saturating integer addition. Computes `self + rhs`, saturating at the
 /// numeric bounds instead of overflowing.
 ///
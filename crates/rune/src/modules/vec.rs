@@ -3,6 +3,7 @@
 use core::cmp::Ordering;
 
 use crate as rune;
+use crate::alloc;
 use crate::alloc::prelude::*;
 use crate::runtime::{
     EnvProtocolCaller, Formatter, Function, Hasher, Iterator, Ref, TypeOf, Value, Vec, VmErrorKind,
@@ -45,7 +46,10 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(insert)?;
     m.function_meta(clone)?;
     m.function_meta(sort_by)?;
+    m.function_meta(sort_by_key)?;
     m.function_meta(sort)?;
+    m.function_meta(is_sorted)?;
+    m.function_meta(is_sorted_by)?;
     m.function_meta(into_iter)?;
     m.function_meta(index_set)?;
     m.function_meta(index_get)?;
@@ -223,6 +227,66 @@ fn sort_by(vec: &mut Vec, comparator: &Function) -> VmResult<()> {
     }
 }
 
+/// Sort a vector with a key extraction function.
+///
+/// The key function is called once for each element, and the keys are
+/// compared using the [`CMP`] protocol. The sort is stable, so elements with
+/// equal keys keep their relative order.
+///
+/// # Panics
+///
+/// If the key function errors, or the keys are not comparable, sorting stops
+/// and the error is propagated.
+///
+/// ```rune,should_panic
+/// let values = [1, 2, 3];
+/// values.sort_by_key(|v| if v == 2 { panic!("bad key") } else { v });
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let values = [#{ name: "c", age: 20 }, #{ name: "a", age: 30 }, #{ name: "b", age: 20 }];
+///
+/// values.sort_by_key(|v| v.age);
+/// assert_eq!(values.iter().map(|v| v.name).collect::<Vec>(), ["c", "b", "a"]);
+///
+/// values.sort_by_key(|v| v.name);
+/// assert_eq!(values.iter().map(|v| v.name).collect::<Vec>(), ["a", "b", "c"]);
+/// ```
+#[rune::function(instance)]
+fn sort_by_key(vec: &mut Vec, key: &Function) -> VmResult<()> {
+    let mut keyed = vm_try!(alloc::Vec::try_with_capacity(vec.len()));
+
+    for value in vec.iter() {
+        let k = vm_try!(key.call::<_, Value>((value.clone(),)));
+        vm_try!(keyed.try_push((k, value.clone())));
+    }
+
+    let mut error = None;
+
+    keyed.sort_by(|(a, _), (b, _)| match Value::cmp(a, b) {
+        VmResult::Ok(ordering) => ordering,
+        VmResult::Err(e) => {
+            if error.is_none() {
+                error = Some(e);
+            }
+
+            Ordering::Equal
+        }
+    });
+
+    if let Some(e) = error {
+        return VmResult::Err(e);
+    }
+
+    for (slot, (_, value)) in vec.iter_mut().zip(keyed) {
+        *slot = value;
+    }
+
+    VmResult::Ok(())
+}
+
 /// Sort the vector.
 ///
 /// This require all elements to be of the same type, and implement total
@@ -285,6 +349,58 @@ fn sort(vec: &mut Vec) -> VmResult<()> {
     VmResult::Ok(())
 }
 
+/// Test if the elements of the vector are sorted.
+///
+/// Elements are compared using the [`CMP`] protocol, and checking stops at the
+/// first pair of elements which is out of order.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!([1, 2, 2, 3].is_sorted());
+/// assert!(![1, 3, 2].is_sorted());
+/// assert!([].is_sorted());
+/// assert!([1].is_sorted());
+/// ```
+#[rune::function(instance)]
+fn is_sorted(vec: &Vec) -> VmResult<bool> {
+    for window in vec.windows(2) {
+        if vm_try!(Value::cmp(&window[0], &window[1])) == Ordering::Greater {
+            return VmResult::Ok(false);
+        }
+    }
+
+    VmResult::Ok(true)
+}
+
+/// Test if the elements of the vector are sorted according to the specified
+/// comparator function.
+///
+/// Checking stops at the first pair of elements which is out of order.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::cmp;
+///
+/// assert!([3, 2, 1].is_sorted_by(|a, b| cmp(b, a)));
+/// assert!(![1, 2, 3].is_sorted_by(|a, b| cmp(b, a)));
+/// assert!([].is_sorted_by(|a, b| cmp(b, a)));
+/// assert!([1].is_sorted_by(|a, b| cmp(b, a)));
+/// ```
+#[rune::function(instance)]
+fn is_sorted_by(vec: &Vec, comparator: &Function) -> VmResult<bool> {
+    for window in vec.windows(2) {
+        let ordering = vm_try!(comparator.call::<_, Ordering>((&window[0], &window[1])));
+
+        if ordering == Ordering::Greater {
+            return VmResult::Ok(false);
+        }
+    }
+
+    VmResult::Ok(true)
+}
+
 /// Clears the vector, removing all values.
 ///
 /// Note that this method has no effect on the allocated capacity of the vector.
@@ -1416,7 +1416,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
 
                 let mut const_compiler = ir::Interpreter {
                     budget: ir::Budget::new(1_000_000),
-                    scopes: ir::Scopes::new()?,
+                    scopes: ir::Scopes::with_max_depth(self.options.max_const_depth)?,
                     module: item_meta.module,
                     item: item_meta.item,
                     q: self.borrow(),
@@ -1455,7 +1455,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
 
                 let mut const_compiler = ir::Interpreter {
                     budget: ir::Budget::new(1_000_000),
-                    scopes: ir::Scopes::new()?,
+                    scopes: ir::Scopes::with_max_depth(self.options.max_const_depth)?,
                     module: item_meta.module,
                     item: item_meta.item,
                     q: self.borrow(),
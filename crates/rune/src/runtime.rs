@@ -26,6 +26,8 @@ pub use self::bytes::Bytes;
 mod call;
 pub use self::call::Call;
 
+pub mod cmp_depth;
+
 mod const_value;
 pub use self::const_value::ConstValue;
 
@@ -0,0 +1,91 @@
+//! Recursion limit for comparing values.
+//!
+//! Comparing values such as vectors, tuples and objects recurses into their
+//! elements, which for deeply nested or self-referential values could
+//! otherwise overflow the native stack. To guard against this, a comparison
+//! through [`Value::cmp`] fails with an error once it recurses deeper than the
+//! configured limit, which defaults to [`DEFAULT_LIMIT`].
+//!
+//! The limit is tracked per thread, and is only enforced when the `std` feature
+//! is enabled.
+//!
+//! [`Value::cmp`]: crate::runtime::Value::cmp
+
+use crate::runtime::VmResult;
+
+/// The default depth to which comparisons are allowed to recurse.
+pub const DEFAULT_LIMIT: usize = 128;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static LIMIT: core::cell::Cell<usize> = const { core::cell::Cell::new(DEFAULT_LIMIT) };
+    static DEPTH: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+/// Call the given closure with comparisons limited to recurse at most `limit`
+/// levels deep.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{cmp_depth, Value};
+///
+/// let value = rune::to_value((1, (2, (3,))))?;
+///
+/// assert!(cmp_depth::with(3, || Value::cmp(&value, &value)).into_result().is_ok());
+/// assert!(cmp_depth::with(2, || Value::cmp(&value, &value)).is_err());
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+pub fn with<F, T>(limit: usize, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    #[cfg(feature = "std")]
+    {
+        struct Restore(usize);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                LIMIT.with(|tls| tls.set(self.0));
+            }
+        }
+
+        let _restore = Restore(LIMIT.with(|tls| tls.replace(limit)));
+        f()
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = limit;
+        f()
+    }
+}
+
+/// Guard for one level of comparison recursion, which is released when
+/// dropped.
+pub(crate) struct Guard(());
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        DEPTH.with(|tls| tls.set(tls.get().saturating_sub(1)));
+    }
+}
+
+/// Enter one level of comparison recursion, erroring if this would exceed the
+/// limit.
+pub(crate) fn enter() -> VmResult<Guard> {
+    #[cfg(feature = "std")]
+    {
+        let limit = LIMIT.with(|tls| tls.get());
+        let depth = DEPTH.with(|tls| tls.get());
+
+        if depth >= limit {
+            return VmResult::err(crate::runtime::VmErrorKind::ComparisonDepthExceeded { limit });
+        }
+
+        DEPTH.with(|tls| tls.set(depth + 1));
+    }
+
+    VmResult::Ok(Guard(()))
+}
@@ -40,6 +40,26 @@ pub(crate) struct EnvProtocolCaller;
 
 impl ProtocolCaller for EnvProtocolCaller {
     fn call_protocol_fn<A>(&mut self, protocol: Protocol, target: Value, args: A) -> VmResult<Value>
+    where
+        A: GuardedArgs,
+    {
+        match vm_try!(self.try_call_protocol_fn(protocol, target, args)) {
+            CallResult::Ok(value) => VmResult::Ok(value),
+            CallResult::Unsupported(target) => {
+                VmResult::err(VmErrorKind::MissingInstanceFunction {
+                    hash: Hash::associated_function(vm_try!(target.type_hash()), protocol.hash),
+                    instance: vm_try!(target.type_info()),
+                })
+            }
+        }
+    }
+
+    fn try_call_protocol_fn<A>(
+        &mut self,
+        protocol: Protocol,
+        target: Value,
+        args: A,
+    ) -> VmResult<CallResult<Value>>
     where
         A: GuardedArgs,
     {
@@ -63,14 +83,11 @@ impl ProtocolCaller for EnvProtocolCaller {
 
                 let mut vm = Vm::with_stack(context.clone(), unit.clone(), stack);
                 vm.set_ip(offset);
-                return call.call_with_vm(vm);
+                return VmResult::Ok(CallResult::Ok(vm_try!(call.call_with_vm(vm))));
             }
 
             let Some(handler) = context.function(hash) else {
-                return VmResult::err(VmErrorKind::MissingInstanceFunction {
-                    hash,
-                    instance: vm_try!(target.type_info()),
-                });
+                return VmResult::Ok(CallResult::Unsupported(target));
             };
 
             let mut stack = vm_try!(Stack::with_capacity(count));
@@ -80,7 +97,7 @@ impl ProtocolCaller for EnvProtocolCaller {
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut stack)) };
 
             vm_try!(handler(&mut stack, count));
-            VmResult::Ok(vm_try!(stack.pop()))
+            VmResult::Ok(CallResult::Ok(vm_try!(stack.pop())))
         });
 
         /// Check that arguments matches expected or raise the appropriate error.
@@ -111,4 +128,22 @@ impl ProtocolCaller for Vm {
 
         VmResult::Ok(vm_try!(self.stack_mut().pop()))
     }
+
+    fn try_call_protocol_fn<A>(
+        &mut self,
+        protocol: Protocol,
+        target: Value,
+        args: A,
+    ) -> VmResult<CallResult<Value>>
+    where
+        A: GuardedArgs,
+    {
+        if let CallResult::Unsupported(target) =
+            vm_try!(self.call_instance_fn(target, protocol, args))
+        {
+            return VmResult::Ok(CallResult::Unsupported(target));
+        }
+
+        VmResult::Ok(CallResult::Ok(vm_try!(self.stack_mut().pop())))
+    }
 }
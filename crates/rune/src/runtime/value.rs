@@ -15,11 +15,11 @@ use crate::alloc::{self, String};
 use crate::compile::ItemBuf;
 use crate::runtime::vm::CallResult;
 use crate::runtime::{
-    AccessKind, AnyObj, Bytes, ConstValue, ControlFlow, EnvProtocolCaller, Format, Formatter,
-    FromValue, FullTypeOf, Function, Future, Generator, GeneratorState, Iterator, MaybeTypeOf, Mut,
-    Object, OwnedTuple, Protocol, ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive,
-    RangeTo, RangeToInclusive, RawMut, RawRef, Ref, Shared, Stream, ToValue, Type, TypeInfo,
-    Variant, Vec, Vm, VmError, VmErrorKind, VmIntegerRepr, VmResult,
+    cmp_depth, AccessKind, AnyObj, Bytes, ConstValue, ControlFlow, EnvProtocolCaller, Format,
+    Formatter, FromValue, FullTypeOf, Function, Future, Generator, GeneratorState, Iterator,
+    MaybeTypeOf, Mut, Object, OwnedTuple, Protocol, ProtocolCaller, Range, RangeFrom, RangeFull,
+    RangeInclusive, RangeTo, RangeToInclusive, RawMut, RawRef, Ref, Shared, Stream, ToValue, Type,
+    TypeInfo, Variant, Vec, Vm, VmError, VmErrorKind, VmIntegerRepr, VmResult,
 };
 #[cfg(feature = "alloc")]
 use crate::runtime::{Hasher, Tuple};
@@ -35,6 +35,39 @@ where
     VmResult::err(error)
 }
 
+/// Compare an integer with a float without losing precision, returning `None`
+/// if the float is `NaN`.
+///
+/// Promoting the integer to a float would round integers which aren't exactly
+/// representable, so the integral and fractional parts of the float are
+/// compared separately instead.
+fn cmp_integer_float(a: i64, b: f64) -> Option<Ordering> {
+    // NB: 2^63 is the smallest float which is out of range of an i64, while
+    // -2^63 is exactly `i64::MIN`.
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+
+    if b.is_nan() {
+        return None;
+    }
+
+    if b >= LIMIT {
+        return Some(Ordering::Less);
+    }
+
+    if b < -LIMIT {
+        return Some(Ordering::Greater);
+    }
+
+    // NB: the float is in range, so this truncates it towards zero exactly.
+    // It avoids `f64::trunc`, which isn't available without `std`.
+    let integral = b as i64;
+
+    match a.cmp(&integral) {
+        Ordering::Equal => 0.0.partial_cmp(&(b - integral as f64)),
+        ordering => Some(ordering),
+    }
+}
+
 /// A empty with a well-defined type.
 pub struct EmptyStruct {
     /// The type hash of the empty.
@@ -1591,6 +1624,9 @@ impl Value {
     /// External types will use the [`Protocol::PARTIAL_CMP`] protocol when
     /// invoked through this function.
     ///
+    /// Integers and floats can be compared with each other, which is done
+    /// without rounding the integer.
+    ///
     /// # Errors
     ///
     /// This function will error if called outside of a virtual machine context.
@@ -1612,6 +1648,10 @@ impl Value {
             (Self::Char(a), Self::Char(b)) => return VmResult::Ok(a.partial_cmp(b)),
             (Self::Float(a), Self::Float(b)) => return VmResult::Ok(a.partial_cmp(b)),
             (Self::Integer(a), Self::Integer(b)) => return VmResult::Ok(a.partial_cmp(b)),
+            (Self::Integer(a), Self::Float(b)) => return VmResult::Ok(cmp_integer_float(*a, *b)),
+            (Self::Float(a), Self::Integer(b)) => {
+                return VmResult::Ok(cmp_integer_float(*b, *a).map(Ordering::reverse));
+            }
             (Self::Type(a), Self::Type(b)) => return VmResult::Ok(a.partial_cmp(b)),
             (Self::Bytes(a), Self::Bytes(b)) => {
                 let a = vm_try!(a.borrow_ref());
@@ -1745,6 +1785,9 @@ impl Value {
     /// External types will use the [`Protocol::CMP`] protocol when invoked
     /// through this function.
     ///
+    /// Integers and floats can be compared with each other, which is done
+    /// without rounding the integer.
+    ///
     /// # Errors
     ///
     /// This function will error if called outside of a virtual machine context.
@@ -1760,6 +1803,21 @@ impl Value {
         b: &Value,
         caller: &mut impl ProtocolCaller,
     ) -> VmResult<Ordering> {
+        // NB: only values which might recurse into other values count towards
+        // the depth limit.
+        let _guard = match a {
+            Self::Bool(..)
+            | Self::Byte(..)
+            | Self::Char(..)
+            | Self::Float(..)
+            | Self::Integer(..)
+            | Self::Type(..)
+            | Self::Bytes(..)
+            | Self::String(..)
+            | Self::EmptyTuple => None,
+            _ => Some(vm_try!(cmp_depth::enter())),
+        };
+
         match (a, b) {
             (Self::Bool(a), Self::Bool(b)) => return VmResult::Ok(a.cmp(b)),
             (Self::Byte(a), Self::Byte(b)) => return VmResult::Ok(a.cmp(b)),
@@ -1772,6 +1830,26 @@ impl Value {
                 return VmResult::err(VmErrorKind::IllegalFloatComparison { lhs: *a, rhs: *b });
             }
             (Self::Integer(a), Self::Integer(b)) => return VmResult::Ok(a.cmp(b)),
+            (Self::Integer(a), Self::Float(b)) => {
+                if let Some(ordering) = cmp_integer_float(*a, *b) {
+                    return VmResult::Ok(ordering);
+                }
+
+                return VmResult::err(VmErrorKind::IllegalFloatComparison {
+                    lhs: *a as f64,
+                    rhs: *b,
+                });
+            }
+            (Self::Float(a), Self::Integer(b)) => {
+                if let Some(ordering) = cmp_integer_float(*b, *a) {
+                    return VmResult::Ok(ordering.reverse());
+                }
+
+                return VmResult::err(VmErrorKind::IllegalFloatComparison {
+                    lhs: *a,
+                    rhs: *b as f64,
+                });
+            }
             (Self::Type(a), Self::Type(b)) => return VmResult::Ok(a.cmp(b)),
             (Self::Bytes(a), Self::Bytes(b)) => {
                 let a = vm_try!(a.borrow_ref());
@@ -1884,6 +1962,26 @@ impl Value {
                     CallResult::Ok(value) => return Ordering::from_value(value),
                     CallResult::Unsupported(..) => {}
                 }
+
+                // NB: types which only implement a partial ordering can still
+                // be totally compared, as long as they produce an ordering.
+                match vm_try!(caller.try_call_protocol_fn(
+                    Protocol::PARTIAL_CMP,
+                    a.clone(),
+                    (b.clone(),)
+                )) {
+                    CallResult::Ok(value) => {
+                        if let Some(ordering) = vm_try!(<Option<Ordering>>::from_value(value)) {
+                            return VmResult::Ok(ordering);
+                        }
+
+                        return err(VmErrorKind::MissingOrdering {
+                            lhs: vm_try!(a.type_info()),
+                            rhs: vm_try!(b.type_info()),
+                        });
+                    }
+                    CallResult::Unsupported(..) => {}
+                }
             }
         }
 
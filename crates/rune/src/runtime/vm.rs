@@ -1725,10 +1725,19 @@ impl Vm {
 
         let value = match value {
             Value::Float(value) => Value::from(-value),
-            Value::Integer(value) => Value::from(-value),
+            Value::Integer(value) => match value.checked_neg() {
+                Some(value) => Value::from(value),
+                None => return err(VmErrorKind::Overflow),
+            },
             other => {
-                let operand = vm_try!(other.type_info());
-                return err(VmErrorKind::UnsupportedUnaryOperation { op: "-", operand });
+                if let CallResult::Unsupported(other) =
+                    vm_try!(self.call_instance_fn(other, Protocol::NEG, ()))
+                {
+                    let operand = vm_try!(other.type_info());
+                    return err(VmErrorKind::UnsupportedUnaryOperation { op: "-", operand });
+                }
+
+                return VmResult::Ok(());
             }
         };
 
@@ -102,7 +102,6 @@ impl VmErrorAt {
         self.index
     }
 
-    #[cfg(feature = "emit")]
     pub(crate) fn kind(&self) -> &VmErrorKind {
         &self.kind
     }
@@ -503,6 +502,18 @@ pub(crate) enum VmErrorKind {
         op: &'static str,
         operand: TypeInfo,
     },
+    UnsupportedComparison {
+        function: &'static str,
+        lhs: TypeInfo,
+        rhs: TypeInfo,
+    },
+    MissingOrdering {
+        lhs: TypeInfo,
+        rhs: TypeInfo,
+    },
+    ComparisonDepthExceeded {
+        limit: usize,
+    },
     MissingStaticString {
         slot: usize,
     },
@@ -704,6 +715,18 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::UnsupportedUnaryOperation { op, operand } => {
                 write!(f, "Unsupported unary operation `{op}` on {operand}",)
             }
+            VmErrorKind::UnsupportedComparison { function, lhs, rhs } => {
+                write!(f, "Cannot compare `{lhs}` with `{rhs}` in `{function}`",)
+            }
+            VmErrorKind::MissingOrdering { lhs, rhs } => {
+                write!(
+                    f,
+                    "Partial comparison between `{lhs}` and `{rhs}` produced no ordering",
+                )
+            }
+            VmErrorKind::ComparisonDepthExceeded { limit } => {
+                write!(f, "Comparison recursion limit of {limit} exceeded")
+            }
             VmErrorKind::MissingStaticString { slot } => {
                 write!(f, "Static string slot `{slot}` does not exist",)
             }
@@ -844,7 +867,7 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::IllegalFloatComparison { lhs, rhs } => {
                 write!(
                     f,
-                    "Cannot perform a comparison of the floats {lhs} and {rhs}",
+                    "Cannot perform a comparison of the numbers {lhs} and {rhs}",
                 )
             }
             #[cfg(feature = "alloc")]
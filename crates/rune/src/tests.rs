@@ -408,6 +408,7 @@ mod bug_454;
 mod bugfixes;
 mod capture;
 mod char;
+mod cmp;
 mod collections;
 mod comments;
 mod compiler_docs;
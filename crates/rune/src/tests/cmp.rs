@@ -0,0 +1,968 @@
+prelude!();
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+use VmErrorKind::*;
+
+/// The result of a partial comparison, as a single type for `assert_vm_error!`.
+type PartialOrdering = Option<Ordering>;
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Any, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Version(i64);
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Version>()?;
+    module.function("version", Version).build()?;
+    module.associated_function("get", |this: &Version| this.0)?;
+    module.associated_function(Protocol::CMP, |a: &Version, b: &Version| {
+        COMPARISONS.with(|c| c.set(c.get() + 1));
+        a.cmp(b)
+    })?;
+    Ok(module)
+}
+
+#[test]
+fn clamp_custom_type() -> Result<()> {
+    let m = make_module()?;
+
+    let values: (i64, i64, i64) = rune_n! {
+        &m,
+        (),
+        (i64, i64, i64) => pub fn main() {
+            let low = version(2);
+            let high = version(4);
+
+            let a = std::cmp::clamp(version(1), low, high);
+            let b = std::cmp::clamp(version(3), low, high);
+            let c = std::cmp::clamp(version(5), low, high);
+            (a.get(), b.get(), c.get())
+        }
+    };
+
+    assert_eq!(values, (2, 3, 4));
+    Ok(())
+}
+
+#[test]
+fn clamp_bad_bounds() {
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::clamp(1, 2, 1) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "`low` must be less than or equal to `high` in `clamp`");
+        }
+    );
+}
+
+#[test]
+fn minmax_compares_once() -> Result<()> {
+    let m = make_module()?;
+
+    COMPARISONS.with(|c| c.set(0));
+
+    let values: (i64, i64) = rune_n! {
+        &m,
+        (),
+        (i64, i64) => pub fn main() {
+            let (min, max) = std::cmp::minmax(version(3), version(1));
+            (min.get(), max.get())
+        }
+    };
+
+    assert_eq!(values, (1, 3));
+    assert_eq!(COMPARISONS.with(Cell::get), 1);
+    Ok(())
+}
+
+#[test]
+fn partial_cmp_floats() {
+    let values: (Option<Ordering>, Option<Ordering>, Option<Ordering>) = rune! {
+        pub fn main() {
+            let a = std::cmp::partial_cmp(f64::NAN, 1.0);
+            let b = std::cmp::partial_cmp(1.0, f64::NAN);
+            let c = std::cmp::partial_cmp(1.0, 2.0);
+            (a, b, c)
+        }
+    };
+
+    assert_eq!(values, (None, None, Some(Ordering::Less)));
+
+    let value: Option<Ordering> = rune! {
+        pub fn main() {
+            std::cmp::partial_cmp(2.5, 2.5)
+        }
+    };
+
+    assert_eq!(value, Some(Ordering::Equal));
+}
+
+#[test]
+fn ordering_int_round_trip() {
+    let values: (Ordering, Ordering, Ordering) = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            let a = Ordering::from_int(Ordering::Less.as_int());
+            let b = Ordering::from_int(Ordering::Equal.as_int());
+            let c = Ordering::from_int(Ordering::Greater.as_int());
+            (a, b, c)
+        }
+    };
+
+    assert_eq!(values, (Ordering::Less, Ordering::Equal, Ordering::Greater));
+}
+
+#[test]
+fn max_by_bad_comparator() {
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::max_by(1, 2, |a, b| a + b) }"#,
+        Expected { .. } => {}
+    );
+}
+
+#[test]
+fn by_key_calls_key_once() -> Result<()> {
+    let m = make_module()?;
+
+    COMPARISONS.with(|c| c.set(0));
+
+    let values: (i64, i64) = rune_n! {
+        &m,
+        (),
+        (i64, i64) => pub fn main() {
+            let calls = [];
+            let key = |v| { calls.push(v.get()); v };
+            let max = std::cmp::max_by_key(version(1), version(2), key);
+            (max.get(), calls.len())
+        }
+    };
+
+    assert_eq!(values, (2, 2));
+    assert_eq!(COMPARISONS.with(Cell::get), 1);
+    Ok(())
+}
+
+#[test]
+fn cmp_mixed_numbers() {
+    let ordering: Ordering = rune!(
+        pub fn main() {
+            std::ops::cmp(1, 1.0)
+        }
+    );
+    assert_eq!(ordering, Ordering::Equal);
+
+    let ordering: Option<Ordering> = rune!(
+        pub fn main() {
+            std::cmp::partial_cmp(1, 1.0)
+        }
+    );
+    assert_eq!(ordering, Some(Ordering::Equal));
+
+    let ordering: Option<Ordering> = rune!(
+        pub fn main() {
+            std::cmp::partial_cmp(1, f64::NAN)
+        }
+    );
+    assert_eq!(ordering, None);
+}
+
+#[test]
+fn cmp_incomparable() {
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::max("a", 1) }"#,
+        UnsupportedComparison { function: "max", .. } => {}
+    );
+
+    assert_vm_error!(
+        PartialOrdering => r#"pub fn main() { std::cmp::partial_cmp([1], (1,)) }"#,
+        Expected { .. } => {}
+    );
+}
+
+#[test]
+fn ordering_all() {
+    let values: Vec<Ordering> = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            let all = Ordering::all();
+            assert_eq!(all.len(), 3);
+            assert!(all[0] == Ordering::Less);
+            assert!(all[1] == Ordering::Equal);
+            assert!(all[2] == Ordering::Greater);
+            all
+        }
+    };
+
+    assert_eq!(values, [Ordering::Less, Ordering::Equal, Ordering::Greater]);
+}
+
+#[test]
+fn compare_sequences() {
+    let values: (Ordering, Ordering, Ordering, Ordering) = rune! {
+        pub fn main() {
+            let a = std::cmp::compare((1, 2), (1, 3));
+            let b = std::cmp::compare((1, 2), (1, 2));
+            let c = std::cmp::compare((1, 2), (1, 2, 0));
+            let d = std::cmp::compare([1, 3], (1, 2, 0));
+            (a, b, c, d)
+        }
+    };
+
+    assert_eq!(
+        values,
+        (
+            Ordering::Less,
+            Ordering::Equal,
+            Ordering::Less,
+            Ordering::Greater
+        )
+    );
+}
+
+#[test]
+fn ordering_display() {
+    let values: (String, String, String) = rune! {
+        pub fn main() {
+            let a = format!("{}", std::ops::cmp(1, 2));
+            let b = format!("{}", std::ops::cmp(2, 2));
+            let c = format!("{}", std::ops::cmp(3, 2));
+            (a, b, c)
+        }
+    };
+
+    assert_eq!(values, ("Less".into(), "Equal".into(), "Greater".into()));
+}
+
+#[test]
+fn ordering_hash_map_keys() {
+    let values: (i64, i64, i64, usize) = rune! {
+        use std::cmp::Ordering;
+        use std::collections::HashMap;
+
+        pub fn main() {
+            let map = HashMap::new();
+            map.insert(Ordering::Less, 1);
+            map.insert(Ordering::Equal, 2);
+            map.insert(Ordering::Greater, 3);
+            map.insert(std::ops::cmp(1, 2), 4);
+            (map[Ordering::Less], map[Ordering::Equal], map[Ordering::Greater], map.len())
+        }
+    };
+
+    assert_eq!(values, (4, 2, 3, 3));
+}
+
+#[test]
+fn choose_by_ordering() {
+    let values: (i64, i64, i64) = rune! {
+        use std::cmp::{choose, Ordering};
+
+        pub fn main() {
+            (
+                choose(Ordering::Less, 1, 2, 3),
+                choose(Ordering::Equal, 1, 2, 3),
+                choose(Ordering::Greater, 1, 2, 3),
+            )
+        }
+    };
+
+    assert_eq!(values, (1, 2, 3));
+}
+
+#[test]
+fn partial_then_chain() {
+    let values: (Option<Ordering>, Option<Ordering>, i64) = rune! {
+        use std::cmp::{partial_cmp, partial_then};
+
+        pub fn main() {
+            let calls = 0;
+
+            let a = partial_then(partial_cmp(1.0, 1.0), || partial_cmp(2.0, 1.0));
+
+            let b = partial_then(partial_cmp(f64::NAN, 1.0), || {
+                calls += 1;
+                partial_cmp(1.0, 2.0)
+            });
+
+            (a, b, calls)
+        }
+    };
+
+    assert_eq!(values, (Some(Ordering::Greater), None, 0));
+}
+
+#[test]
+fn ordering_eq_int() {
+    let values: Vec<bool> = rune! {
+        use std::cmp::Ordering;
+        use std::ops::{cmp, eq};
+
+        pub fn main() {
+            [
+                cmp(1, 2).as_int() == -1,
+                cmp(2, 2).as_int() == 0,
+                cmp(3, 2).as_int() == 1,
+                cmp(1, 2).as_int() == 0,
+                Ordering::Less == Ordering::Less,
+                eq(Ordering::Less, Ordering::Less),
+            ]
+        }
+    };
+
+    assert_eq!(values, [true, true, true, false, true, true]);
+
+    // Orderings only compare equal to orderings, in either direction.
+    assert_vm_error!(
+        r#"
+        use std::cmp::Ordering;
+        pub fn main() { Ordering::Less == -1 }
+        "#,
+        Expected { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        use std::cmp::Ordering;
+        pub fn main() { -1 == Ordering::Less }
+        "#,
+        Expected { .. } => {}
+    );
+}
+
+#[test]
+fn clamp_all_values() {
+    let values: Vec<i64> = rune! {
+        use std::cmp::clamp_all;
+
+        pub fn main() {
+            clamp_all([7, -10, 3, 0, 12, 5], 0, 5)
+        }
+    };
+
+    assert_eq!(values, [5, 0, 3, 0, 5, 5]);
+
+    assert_vm_error!(
+        r#"
+        use std::cmp::clamp_all;
+
+        pub fn main() {
+            clamp_all([1, "two"], 0, 5)
+        }
+        "#,
+        UnsupportedBinaryOperation { op: "cmp", .. } => {}
+    );
+}
+
+#[test]
+fn minmax_incomparable_message() {
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::max("a", 1) }"#,
+        error @ UnsupportedComparison { .. } => {
+            assert_eq!(error.to_string(), "Cannot compare `String` with `i64` in `max`");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::min(1, "a") }"#,
+        error @ UnsupportedComparison { .. } => {
+            assert_eq!(error.to_string(), "Cannot compare `i64` with `String` in `min`");
+        }
+    );
+}
+
+#[test]
+fn ordering_neg() {
+    let values: Vec<Ordering> = rune! {
+        use std::cmp::Ordering;
+        use std::ops::cmp;
+
+        pub fn main() {
+            [-Ordering::Less, -Ordering::Equal, -Ordering::Greater, -cmp(1, 2)]
+        }
+    };
+
+    assert_eq!(
+        values,
+        [
+            Ordering::Greater,
+            Ordering::Equal,
+            Ordering::Less,
+            Ordering::Greater
+        ]
+    );
+}
+
+#[test]
+fn ordering_dir() {
+    let values: Vec<Ordering> = rune! {
+        use std::cmp::{dir, Ordering};
+
+        pub fn main() {
+            let orderings = [Ordering::Less, Ordering::Equal, Ordering::Greater];
+            let out = [];
+
+            for ascending in [true, false] {
+                for ordering in orderings {
+                    out.push(dir(ordering, ascending));
+                }
+            }
+
+            out
+        }
+    };
+
+    assert_eq!(
+        values,
+        [
+            Ordering::Less,
+            Ordering::Equal,
+            Ordering::Greater,
+            Ordering::Greater,
+            Ordering::Equal,
+            Ordering::Less
+        ]
+    );
+}
+
+#[test]
+fn ordering_reverse() {
+    let values: Vec<Ordering> = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            [Ordering::Less.reverse(), Ordering::Equal.reverse(), Ordering::Greater.reverse()]
+        }
+    };
+
+    assert_eq!(values, [Ordering::Greater, Ordering::Equal, Ordering::Less]);
+}
+
+#[test]
+fn ordering_then() {
+    let values: Vec<Ordering> = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            let orderings = [Ordering::Less, Ordering::Equal, Ordering::Greater];
+            let out = [];
+
+            for a in orderings {
+                for b in orderings {
+                    out.push(a.then(b));
+                }
+            }
+
+            out
+        }
+    };
+
+    assert_eq!(
+        values,
+        [
+            Ordering::Less,
+            Ordering::Less,
+            Ordering::Less,
+            Ordering::Less,
+            Ordering::Equal,
+            Ordering::Greater,
+            Ordering::Greater,
+            Ordering::Greater,
+            Ordering::Greater
+        ]
+    );
+}
+
+#[test]
+fn ordering_then_with() {
+    let out: (Vec<Ordering>, i64) = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            let calls = [];
+            let out = [];
+
+            for ordering in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+                out.push(ordering.then_with(|| {
+                    calls.push(ordering);
+                    Ordering::Greater
+                }));
+            }
+
+            (out, calls.len())
+        }
+    };
+
+    // The closure is only called for `Equal`.
+    assert_eq!(
+        out,
+        (
+            vec![Ordering::Less, Ordering::Greater, Ordering::Greater],
+            1
+        )
+    );
+
+    let names: Vec<String> = rune! {
+        use std::ops::cmp;
+
+        pub fn main() {
+            let people = [("Bob", 30), ("Alice", 30), ("Carol", 25)];
+            people.sort_by(|a, b| cmp(b.1, a.1).then(cmp(a.0, b.0)));
+            people.iter().map(|p| p.0).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(names, ["Alice", "Bob", "Carol"]);
+}
+
+#[test]
+fn ordering_then_cmp() {
+    let names: Vec<String> = rune! {
+        use std::ops::cmp;
+
+        struct Person {
+            name,
+            age,
+        }
+
+        pub fn main() {
+            let people = [
+                Person { name: "Bob", age: 30 },
+                Person { name: "Alice", age: 30 },
+                Person { name: "Carol", age: 25 },
+            ];
+
+            people.sort_by(|a, b| cmp(a.age, b.age).then_cmp(a.name, b.name));
+            people.iter().map(|p| p.name).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(names, ["Carol", "Alice", "Bob"]);
+}
+
+#[test]
+fn rank() {
+    let values: Vec<i64> = rune! {
+        use std::cmp::rank;
+
+        pub fn main() {
+            [rank(1, 2), rank(2, 2), rank(3, 2), rank("a", "b"), rank((1, 2), (1, 1))]
+        }
+    };
+
+    assert_eq!(values, [0, 1, 2, 0, 2]);
+}
+
+/// Constants are stored as `ConstValue`, which can't hold external types such
+/// as `Ordering`.
+#[test]
+fn ordering_constant_unsupported() {
+    let mut m = Module::new();
+    let result = m.constant("GREATER", Ordering::Greater).build();
+    assert!(matches!(result, Err(ContextError::ValueError { .. })));
+}
+
+#[test]
+fn equal_by_case_insensitive() -> Result<()> {
+    let mut m = Module::new();
+    m.function("lowercase", |s: &str| s.to_lowercase())
+        .build()?;
+
+    let values: Vec<bool> = rune_n! {
+        &m,
+        (),
+        Vec<bool> =>
+        pub fn main() {
+            let eq = |a, b| lowercase(a) == lowercase(b);
+            [std::cmp::equal_by("Hello", "hELLO", eq), std::cmp::equal_by("Hello", "World", eq)]
+        }
+    };
+
+    assert_eq!(values, [true, false]);
+
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::equal_by(1, 2, |a, b| a + b) }"#,
+        Expected { .. } => {}
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ordering_parse() {
+    let values: Vec<Result<Ordering, String>> = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            ["less", "Equal", "GREATER", "sideways"].iter().map(Ordering::parse).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(
+        values,
+        [
+            Ok(Ordering::Less),
+            Ok(Ordering::Equal),
+            Ok(Ordering::Greater),
+            Err(String::from(
+                "Unknown ordering `sideways`, expected one of `less`, `equal` or `greater`"
+            )),
+        ]
+    );
+}
+
+/// A type which only implements `PARTIAL_CMP`, with negative values being
+/// incomparable.
+#[derive(Debug, Any, Clone, Copy)]
+struct Partial(i64);
+
+#[test]
+fn cmp_falls_back_to_partial_cmp() -> Result<()> {
+    let mut m = Module::new();
+    m.ty::<Partial>()?;
+    m.function("partial", Partial).build()?;
+    m.associated_function("get", |this: &Partial| this.0)?;
+    m.associated_function(Protocol::PARTIAL_CMP, |a: &Partial, b: &Partial| {
+        if a.0 < 0 || b.0 < 0 {
+            return None;
+        }
+
+        a.0.partial_cmp(&b.0)
+    })?;
+
+    let values: (i64, i64, i64) = rune_n! {
+        &m,
+        (),
+        (i64, i64, i64) => pub fn main() {
+            let a = std::cmp::min(partial(3), partial(1)).get();
+            let b = std::cmp::max(partial(3), partial(1)).get();
+            let c = std::cmp::clamp(partial(7), partial(0), partial(5)).get();
+            (a, b, c)
+        }
+    };
+
+    assert_eq!(values, (1, 3, 5));
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let mut sources =
+        crate::tests::sources("pub fn main() { std::cmp::max(partial(-1), partial(1)) }");
+    let mut diagnostics = Default::default();
+    let result = crate::tests::run_helper::<_, _, ()>(
+        &context,
+        &mut sources,
+        &mut diagnostics,
+        ["main"],
+        (),
+    );
+
+    let Err(crate::tests::TestError::VmError(error)) = result else {
+        panic!("Expected a vm error");
+    };
+
+    assert_eq!(
+        error.into_kind().to_string(),
+        "Partial comparison between `Partial` and `Partial` produced no ordering"
+    );
+    Ok(())
+}
+
+#[derive(Debug, Any, PartialEq, Eq, PartialOrd, Ord)]
+struct Priority(i64);
+
+#[test]
+fn type_with_ord() -> Result<()> {
+    let mut m = Module::new();
+    m.ty::<Priority>()?.with_ord()?;
+    m.function("priority", Priority).build()?;
+    m.associated_function("get", |this: &Priority| this.0)?;
+
+    let values: (Vec<i64>, bool, bool) = rune_n! {
+        &m,
+        (),
+        (Vec<i64>, bool, bool) => pub fn main() {
+            let values = [priority(3), priority(1), priority(2)];
+            values.sort();
+            let values = values.iter().map(|p| p.get()).collect::<Vec>();
+            (values, priority(1) == priority(1), priority(1) < priority(2))
+        }
+    };
+
+    assert_eq!(values, (vec![1, 2, 3], true, true));
+    Ok(())
+}
+
+#[test]
+fn vec_is_sorted_short_circuits() {
+    let out: (bool, i64) = rune! {
+        use std::ops::cmp;
+
+        pub fn main() {
+            let calls = [];
+
+            let sorted = [2, 1, 3, 4].is_sorted_by(|a, b| {
+                calls.push(a);
+                cmp(a, b)
+            });
+
+            (sorted, calls.len())
+        }
+    };
+
+    assert_eq!(out, (false, 1));
+}
+
+#[test]
+fn vec_is_sorted_by_propagates_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2].is_sorted_by(|a, b| panic!("boom")) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "boom");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, "a"].is_sorted() }"#,
+        Expected { .. } => {}
+    );
+}
+
+#[test]
+fn cmp_depth_exceeded() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let a = [];
+            a.push(a);
+            std::cmp::max(a, a)
+        }
+        "#,
+        ComparisonDepthExceeded { limit } => {
+            assert_eq!(limit, rune::runtime::cmp_depth::DEFAULT_LIMIT);
+        }
+    );
+}
+
+#[test]
+fn cmp_depth_configured() {
+    let source = r#"
+        pub fn main(depth) {
+            let a = [];
+
+            for _ in 0..depth {
+                a = [a];
+            }
+
+            std::cmp::max(a, a);
+        }
+    "#;
+
+    let context = Context::with_default_modules().unwrap();
+
+    let result: Result<()> =
+        rune::runtime::cmp_depth::with(8, || run(&context, source, ["main"], (7i64,)));
+    assert!(result.is_ok());
+
+    let result: Result<()> =
+        rune::runtime::cmp_depth::with(8, || run(&context, source, ["main"], (8i64,)));
+    let error = result.unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("Comparison recursion limit of 8 exceeded"));
+}
+
+#[test]
+fn min_max_of() {
+    let values: (Option<i64>, Option<i64>, Option<i64>, Option<i64>) = rune! {
+        use std::cmp::{max_of, min_of};
+
+        pub fn main() {
+            (min_of([3, -1, 2]), max_of([3, -1, 2]), min_of([7]), max_of([7]))
+        }
+    };
+
+    assert_eq!(values, (Some(-1), Some(3), Some(7), Some(7)));
+
+    let values: (Option<i64>, Option<i64>) = rune! {
+        pub fn main() {
+            (std::cmp::min_of([]), std::cmp::max_of([]))
+        }
+    };
+
+    assert_eq!(values, (None, None));
+
+    let values: (i64, i64) = rune! {
+        use std::cmp::{max_of, min_of};
+
+        pub fn main() {
+            let (a, b, c, d) = ([1], [1], [1], [1]);
+            min_of([a, b]).unwrap().push(2);
+            max_of([c, d]).unwrap().push(2);
+            (a.len(), d.len())
+        }
+    };
+
+    // Equal minimums keep the first, equal maximums keep the last.
+    assert_eq!(values, (2, 2));
+}
+
+#[test]
+fn min_max_of_incomparable() {
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::min_of([1, "a"]) }"#,
+        error @ UnsupportedComparison { function: "min_of", .. } => {
+            assert_eq!(error.to_string(), "Cannot compare `i64` with `String` in `min_of`");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::cmp::max_of(["a", 1]) }"#,
+        UnsupportedComparison { function: "max_of", .. } => {}
+    );
+}
+
+#[test]
+fn cmp_integer_float() {
+    let values: Vec<Ordering> = rune! {
+        use std::ops::cmp;
+
+        pub fn main() {
+            [
+                cmp(3, 3.5),
+                cmp(3, 3.0),
+                cmp(3.5, 3),
+                cmp(-3, -3.5),
+                cmp(-3.5, -3),
+                cmp(9007199254740993, 9007199254740992.0),
+                cmp(9007199254740992.0, 9007199254740993),
+                cmp(9223372036854775807, 9223372036854775807.0),
+                cmp(-9223372036854775807 - 1, -9223372036854775808.0),
+                cmp(0, f64::INFINITY),
+                cmp(0, f64::NEG_INFINITY),
+            ]
+        }
+    };
+
+    use Ordering::*;
+
+    assert_eq!(
+        values,
+        [Less, Equal, Greater, Greater, Less, Greater, Less, Less, Equal, Less, Greater]
+    );
+
+    let values: (i64, f64, f64) = rune! {
+        use std::cmp::{max, min};
+
+        pub fn main() {
+            (min(3, 3.5), max(3, 3.5), max(3, 3.0))
+        }
+    };
+
+    assert_eq!(values, (3, 3.5, 3.0));
+
+    assert_vm_error!(
+        Ordering => r#"pub fn main() { std::ops::cmp(3, f64::NAN) }"#,
+        IllegalFloatComparison { lhs, rhs } => {
+            assert_eq!(lhs, 3.0);
+            assert!(rhs.is_nan());
+        }
+    );
+
+    let error = IllegalFloatComparison {
+        lhs: 3.0,
+        rhs: f64::NAN,
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Cannot perform a comparison of the numbers 3 and NaN"
+    );
+}
+
+#[test]
+fn cmp_integer_float_operators() {
+    let values: Vec<bool> = rune! {
+        pub fn main() {
+            let one = 1;
+            let half = 1.5;
+            let nan = f64::NAN;
+
+            [
+                one < half,
+                one <= 1.0,
+                one > half,
+                one >= 1.0,
+                half > one,
+                half < one,
+                9007199254740993 > 9007199254740992.0,
+                one < nan,
+                one >= nan,
+            ]
+        }
+    };
+
+    assert_eq!(
+        values,
+        [true, true, false, true, true, false, true, false, false]
+    );
+}
+
+#[test]
+fn ordering_variants() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let variants = context.enum_variants(Ordering::type_hash())?;
+    assert_eq!(variants, [(0, "Less"), (1, "Equal"), (2, "Greater")]);
+
+    assert!(context
+        .enum_variants(<i64 as TypeOf>::type_hash())?
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn reverse_sort() {
+    let values: Vec<i64> = rune! {
+        use std::cmp::Reverse;
+
+        pub fn main() {
+            let values = [2, 1, 3, 2, 1].iter().map(|v| Reverse(v)).collect::<Vec>();
+            values.sort();
+            values.iter().map(|v| v.0).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(values, [3, 2, 2, 1, 1]);
+
+    let names: Vec<String> = rune! {
+        use std::cmp::Reverse;
+        use std::ops::cmp;
+
+        pub fn main() {
+            let items = [("a", 2), ("b", 1), ("c", 2), ("d", 3), ("e", 1)];
+            items.sort_by(|a, b| cmp(Reverse(a.1), Reverse(b.1)));
+            items.iter().map(|item| item.0).collect::<Vec>()
+        }
+    };
+
+    // Ties keep their original order, since sorting is stable.
+    assert_eq!(names, ["d", "a", "c", "b", "e"]);
+
+    let values: Vec<i64> = rune! {
+        use std::cmp::Reverse;
+
+        pub fn main() {
+            let values = [2, 1, 3, 2].iter().map(|v| Reverse(Reverse(v))).collect::<Vec>();
+            values.sort();
+            values.iter().map(|v| (v.0).0).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(values, [1, 2, 2, 3]);
+}
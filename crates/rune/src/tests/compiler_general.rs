@@ -18,6 +18,115 @@ fn break_outside_of_loop() {
         r#"pub fn main() { break; }"#,
         span!(16, 21), BreakOutsideOfLoop
     };
+
+    assert_errors! {
+        r#"pub fn main() { break 1 + 2; }"#,
+        span!(16, 21), BreakOutsideOfLoop
+    };
+
+    assert_errors! {
+        r#"pub fn main() { 'b: { break 1; } }"#,
+        span!(22, 27), BreakOutsideOfLoop
+    };
+}
+
+#[test]
+fn break_errors_are_collected() {
+    let mut diagnostics = Default::default();
+    let source = r#"pub fn main() { if true { break; } continue 'a; }"#;
+    let _ = crate::tests::compile_helper(source, &mut diagnostics).unwrap_err();
+
+    let mut errors = Vec::new();
+
+    for diagnostic in diagnostics.into_diagnostics() {
+        let rune::diagnostics::Diagnostic::Fatal(fatal) = diagnostic else {
+            panic!("expected only fatal diagnostics, got {diagnostic:?}");
+        };
+
+        let rune::diagnostics::FatalDiagnosticKind::CompileError(error) = fatal.into_kind() else {
+            panic!("expected a compile error");
+        };
+
+        errors.push(error);
+    }
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(rune::ast::Spanned::span(&errors[0]), span!(26, 31));
+    assert!(matches!(errors[0].kind(), BreakOutsideOfLoop));
+    assert_eq!(rune::ast::Spanned::span(&errors[1]), span!(35, 46));
+    assert!(matches!(errors[1].kind(), ContinueMissingLabel { label } if &**label == "a"));
+}
+
+#[test]
+fn break_errors_in_closures_are_reported_once() {
+    fn errors(source: &str) -> Vec<crate::compile::Error> {
+        let mut diagnostics = Default::default();
+        let _ = crate::tests::compile_helper(source, &mut diagnostics).unwrap_err();
+
+        let mut errors = Vec::new();
+
+        for diagnostic in diagnostics.into_diagnostics() {
+            let rune::diagnostics::Diagnostic::Fatal(fatal) = diagnostic else {
+                panic!("expected only fatal diagnostics, got {diagnostic:?}");
+            };
+
+            let rune::diagnostics::FatalDiagnosticKind::CompileError(error) = fatal.into_kind()
+            else {
+                panic!("expected a compile error");
+            };
+
+            errors.push(error);
+        }
+
+        errors
+    }
+
+    let closure = errors(r#"pub fn main() { let f = || { break; }; }"#);
+    assert_eq!(closure.len(), 1);
+    assert!(matches!(closure[0].kind(), BreakOutsideOfLoop));
+    assert_eq!(rune::ast::Spanned::span(&closure[0]), span!(29, 34));
+
+    let nested = errors(r#"pub fn main() { let f = || || { continue; }; }"#);
+    assert_eq!(nested.len(), 1);
+    assert!(matches!(nested[0].kind(), ContinueOutsideOfLoop));
+
+    let block = errors(r#"pub async fn main() { let f = async { break; }; }"#);
+    assert_eq!(block.len(), 1);
+    assert!(matches!(block[0].kind(), BreakOutsideOfLoop));
+}
+
+#[test]
+fn break_value_in_condition_loop() {
+    assert_errors! {
+        r#"pub fn main() { 'a: while true { break 'a 1; } }"#,
+        span!(33, 43), BreakValueInConditionLoop { label } => {
+            assert_eq!(&*label, "a");
+        }
+    };
+
+    assert_errors! {
+        r#"pub fn main() { 'a: for x in [] { break 'a x; } }"#,
+        span!(34, 44), BreakValueInConditionLoop { label } => {
+            assert_eq!(&*label, "a");
+        }
+    };
+}
+
+#[test]
+fn break_missing_label() {
+    assert_errors! {
+        r#"pub fn main() { 'existing: loop { loop { break 'missing; } } }"#,
+        span!(47, 55), BreakMissingLabel { label } => {
+            assert_eq!(&*label, "missing");
+        }
+    };
+
+    assert_errors! {
+        r#"pub fn main() { break 'missing; }"#,
+        span!(22, 30), BreakMissingLabel { label } => {
+            assert_eq!(&*label, "missing");
+        }
+    };
 }
 
 #[test]
@@ -28,6 +137,16 @@ fn continue_outside_of_loop() {
     };
 }
 
+#[test]
+fn missing_local() {
+    assert_errors! {
+        r#"const X = { let a = 1; { let b = a; } b }; pub fn main() { X }"#,
+        span!(38, 39), MissingLocal { name } => {
+            assert_eq!(&*name, "b");
+        }
+    };
+}
+
 #[test]
 fn test_pointers() {
     assert_errors! {
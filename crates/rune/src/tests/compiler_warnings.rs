@@ -1,5 +1,6 @@
 prelude!();
 
+use diagnostics::WarningDiagnosticKind;
 use diagnostics::WarningDiagnosticKind::*;
 
 #[test]
@@ -25,3 +26,159 @@ fn test_remove_variant_parens() {
         span!(20, 22), RemoveTupleCallParams { variant: span!(16, 20), .. }
     };
 }
+
+#[test]
+fn test_duplicate_loop_label() {
+    assert_warnings! {
+        r#"pub fn main() { 'a: loop { 'a: loop { break 'a; } break; } }"#,
+        span!(27, 29), DuplicateLoopLabel { .. }
+    };
+
+    assert_warnings! {
+        r#"pub fn main() { let f = || { 'a: loop { 'a: loop { break 'a; } break; } }; f }"#,
+        span!(40, 42), DuplicateLoopLabel { .. }
+    };
+}
+
+#[test]
+fn test_unreachable_after_break() {
+    assert_warnings! {
+        r#"pub fn main() { loop { break; foo(); } } fn foo() {}"#,
+        span!(30, 36), UnreachableAfterBreak { .. }
+    };
+
+    assert_warnings! {
+        r#"pub fn main() { return 1; let a = 2; a }"#,
+        span!(26, 36), UnreachableAfterBreak { .. }
+    };
+
+    assert_warnings! {
+        r#"pub fn main() { let f = || { return 1; 2 }; f }"#,
+        span!(39, 40), UnreachableAfterBreak { .. }
+    };
+}
+
+#[test]
+fn test_shadowed_variable() -> Result<()> {
+    fn collect(source: &str, shadow_warnings: bool) -> Result<Vec<WarningDiagnosticKind>> {
+        let context = Context::with_default_modules()?;
+        let mut sources = crate::tests::sources(source);
+
+        let mut options = rune::Options::default();
+        options.shadow_warnings(shadow_warnings);
+
+        let mut diagnostics = Diagnostics::new();
+
+        let _ = rune::prepare(&mut sources)
+            .with_context(&context)
+            .with_options(&options)
+            .with_diagnostics(&mut diagnostics)
+            .build()?;
+
+        let mut warnings = Vec::new();
+
+        for diagnostic in diagnostics.into_diagnostics() {
+            if let diagnostics::Diagnostic::Warning(warning) = diagnostic {
+                warnings.push(warning.into_kind());
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    let source = r#"pub fn main() { let a = 1; { let a = 2; a } }"#;
+
+    let warnings = collect(source, true)?;
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0], ShadowedVariable { span } if span == span!(33, 34)));
+
+    assert!(collect(source, false)?.is_empty());
+
+    let fresh = r#"pub fn main() { let a = 1; let a = a + 1; { let b = 2; a + b } }"#;
+    assert!(collect(fresh, true)?.is_empty());
+
+    let closure =
+        r#"pub fn main() { let a = 1; let f = || { let b = a; { let a = 2; a + b } }; f }"#;
+
+    let warnings = collect(closure, true)?;
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0], ShadowedVariable { span } if span == span!(57, 58)));
+    Ok(())
+}
+
+#[test]
+fn test_used_deprecated() -> Result<()> {
+    #[rune::function(deprecated = "Use `add` instead")]
+    fn plus(a: i64, b: i64) -> i64 {
+        a + b
+    }
+
+    let mut m = Module::new();
+    m.function_meta(plus)?;
+    m.function("minus", |a: i64, b: i64| a - b)
+        .build()?
+        .deprecated("Use `sub` instead")?;
+    m.associated_function("halve", |this: i64| this / 2)?
+        .deprecated("Use `/ 2` instead")?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let warnings = |source: &str| -> Result<Vec<WarningDiagnosticKind>> {
+        let mut sources = crate::tests::sources(source);
+        let mut diagnostics = Diagnostics::new();
+
+        let _ = rune::prepare(&mut sources)
+            .with_context(&context)
+            .with_diagnostics(&mut diagnostics)
+            .build()?;
+
+        let mut warnings = Vec::new();
+
+        for diagnostic in diagnostics.diagnostics() {
+            if let diagnostics::Diagnostic::Warning(warning) = diagnostic {
+                warnings.push(warning.kind);
+            }
+        }
+
+        Ok(warnings)
+    };
+
+    let found = warnings(r#"pub fn main() { plus(1, minus(3, 2)) }"#)?;
+    assert_eq!(found.len(), 2);
+
+    let UsedDeprecated { span, message, .. } = found[0] else {
+        panic!("expected a deprecation warning, got {:?}", found[0]);
+    };
+
+    assert_eq!(span, span!(16, 20));
+    assert_eq!(message, "Use `add` instead");
+    assert_eq!(
+        found[0].to_string(),
+        "Used deprecated function: Use `add` instead"
+    );
+
+    let UsedDeprecated { span, message, .. } = found[1] else {
+        panic!("expected a deprecation warning, got {:?}", found[1]);
+    };
+
+    assert_eq!(span, span!(24, 29));
+    assert_eq!(message, "Use `sub` instead");
+
+    let found = warnings(r#"pub fn main() { let x = 4; x.halve() }"#)?;
+    assert_eq!(found.len(), 1);
+
+    let UsedDeprecated { span, message, .. } = found[0] else {
+        panic!("expected a deprecation warning, got {:?}", found[0]);
+    };
+
+    assert_eq!(span, span!(27, 34));
+    assert_eq!(message, "Use `/ 2` instead");
+
+    let found = warnings(r#"pub fn main() { let f = |x| plus(x, 1).halve(); f(1) }"#)?;
+    assert_eq!(found.len(), 2);
+    assert!(matches!(found[0], UsedDeprecated { span, .. } if span == span!(28, 32)));
+    assert!(matches!(found[1], UsedDeprecated { span, .. } if span == span!(28, 44)));
+
+    Ok(())
+}
@@ -30,6 +30,29 @@ fn test_continue_label() {
     };
 }
 
+#[test]
+fn test_continue_label_nested_for() {
+    let pairs: Vec<(i64, i64)> = rune! {
+        pub fn main() {
+            let pairs = [];
+
+            'outer: for a in 0..3 {
+                for b in 0..3 {
+                    if b > a {
+                        continue 'outer;
+                    }
+
+                    pairs.push((a, b));
+                }
+            }
+
+            pairs
+        }
+    };
+
+    assert_eq!(pairs, [(0, 0), (1, 0), (1, 1), (2, 0), (2, 1), (2, 2)]);
+}
+
 #[test]
 fn while_continue() {
     rune! {
@@ -106,6 +129,28 @@ fn for_continue() {
     };
 }
 
+#[test]
+fn test_continue_in_value_position() {
+    let sum: i64 = rune! {
+        pub fn main() {
+            let sum = 0;
+
+            for value in [Some(1), None, Some(2)] {
+                let value = match value {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                sum += value;
+            }
+
+            sum
+        }
+    };
+
+    assert_eq!(sum, 3);
+}
+
 #[test]
 fn test_continue_not_in_loop() {
     assert_errors! {
@@ -118,8 +163,26 @@ fn test_continue_not_in_loop() {
 fn test_continue_missing_label() {
     assert_errors! {
         r#"pub fn main() { 'existing: loop { loop { continue 'missing; } } }"#,
-        span!(41, 58), MissingLoopLabel { label } => {
+        span!(41, 58), ContinueMissingLabel { label } => {
             assert_eq!(&*label, "missing");
         }
     };
 }
+
+#[test]
+fn test_continue_labeled_block() {
+    assert_errors! {
+        r#"pub fn main() { loop { 'b: { continue 'b; } } }"#,
+        span!(29, 40), ContinueLabeledBlock { label } => {
+            assert_eq!(&*label, "b");
+        }
+    };
+}
+
+#[test]
+fn test_continue_in_closure() {
+    assert_errors! {
+        r#"pub fn main() { loop { let f = || { continue; }; f(); break; } }"#,
+        span!(36, 44), ContinueOutsideOfLoop
+    };
+}
@@ -210,3 +210,45 @@ fn construct_struct() -> rune::support::Result<()> {
 
     Ok(())
 }
+
+/// Tests reading an associated constant registered on an external enum.
+#[test]
+fn associated_constant() -> rune::support::Result<()> {
+    #[derive(Debug, Any, PartialEq, Eq)]
+    enum Enum {
+        #[rune(constructor)]
+        First(#[rune(get)] u32),
+    }
+
+    fn make_module() -> Result<Module, ContextError> {
+        let mut module = Module::new();
+        module.ty::<Enum>()?;
+        module
+            .constant("DEFAULT", 42u32)
+            .build_associated::<Enum>()?;
+        Ok(module)
+    }
+
+    let m = make_module()?;
+
+    let mut context = Context::new();
+    context.install(m)?;
+    let runtime = Arc::new(context.runtime()?);
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                Enum::First(Enum::DEFAULT)
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(runtime, Arc::new(unit));
+
+    let output = vm.call(["main"], ())?;
+    let output: Enum = from_value(output)?;
+    assert_eq!(output, Enum::First(42));
+    Ok(())
+}
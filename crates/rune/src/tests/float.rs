@@ -58,3 +58,23 @@ fn test_float_fns() {
     };
     assert_eq!(n, 1728.0);
 }
+
+#[test]
+fn test_float_total_cmp() {
+    let values: Vec<f64> = rune! {
+        pub fn main() {
+            let values = [1.0, f64::NAN, 0.0, -1.0, -0.0, f64::NEG_INFINITY, -f64::NAN];
+            values.sort_by(|a, b| a.total_cmp(b));
+            values
+        }
+    };
+
+    assert_eq!(values.len(), 7);
+    assert!(values[0].is_nan() && values[0].is_sign_negative());
+    assert_eq!(values[1], f64::NEG_INFINITY);
+    assert_eq!(values[2], -1.0);
+    assert!(values[3] == 0.0 && values[3].is_sign_negative());
+    assert!(values[4] == 0.0 && values[4].is_sign_positive());
+    assert_eq!(values[5], 1.0);
+    assert!(values[6].is_nan() && values[6].is_sign_positive());
+}
@@ -58,3 +58,78 @@ fn test_int_fns() {
     };
     assert_eq!(n, 1728);
 }
+
+#[test]
+fn test_int_checked_overflow() {
+    let values: Vec<Option<i64>> = rune! {
+        pub fn main() {
+            [
+                (i64::MAX - 1).checked_add(1),
+                i64::MAX.checked_add(1),
+                (i64::MIN + 1).checked_sub(1),
+                i64::MIN.checked_sub(1),
+                (i64::MAX / 2).checked_mul(2),
+                i64::MAX.checked_mul(2),
+            ]
+        }
+    };
+
+    assert_eq!(
+        values,
+        [
+            Some(i64::MAX),
+            None,
+            Some(i64::MIN),
+            None,
+            Some(i64::MAX - 1),
+            None
+        ]
+    );
+}
+
+#[test]
+fn test_int_saturating() {
+    let values: Vec<i64> = rune! {
+        pub fn main() {
+            [
+                i64::MAX.saturating_add(1),
+                i64::MIN.saturating_add(-1),
+                i64::MIN.saturating_sub(1),
+                i64::MAX.saturating_sub(-1),
+                i64::MAX.saturating_mul(2),
+                i64::MIN.saturating_mul(2),
+                (i64::MAX - 1).saturating_add(1),
+            ]
+        }
+    };
+
+    assert_eq!(
+        values,
+        [
+            i64::MAX,
+            i64::MIN,
+            i64::MIN,
+            i64::MAX,
+            i64::MAX,
+            i64::MIN,
+            i64::MAX
+        ]
+    );
+}
+
+#[test]
+fn test_int_wrapping() {
+    let values: Vec<i64> = rune! {
+        pub fn main() {
+            [
+                i64::MAX.wrapping_add(1),
+                i64::MIN.wrapping_sub(1),
+                i64::MAX.wrapping_mul(2),
+                i64::MIN.wrapping_neg(),
+                5.wrapping_neg(),
+            ]
+        }
+    };
+
+    assert_eq!(values, [i64::MIN, i64::MAX, -2, i64::MIN, -5]);
+}
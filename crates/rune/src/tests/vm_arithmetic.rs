@@ -172,3 +172,26 @@ fn test_bitwise_not_u8() {
     );
     assert_eq!(out, !0b10100u8);
 }
+
+#[test]
+fn test_neg_i64() {
+    let out: i64 = rune!(
+        pub fn main() {
+            let a = 10;
+            -a
+        }
+    );
+    assert_eq!(out, -10);
+
+    let out: (Option<i64>, Option<i64>) = rune!(
+        pub fn main() {
+            (10.checked_neg(), i64::MIN.checked_neg())
+        }
+    );
+    assert_eq!(out, (Some(-10), None));
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = i64::MIN; -a }"#,
+        Overflow => {}
+    );
+}
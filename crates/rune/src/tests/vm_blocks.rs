@@ -11,3 +11,61 @@ fn test_anonymous_type_precedence() {
     };
     assert_eq!(out, 3);
 }
+
+#[test]
+fn test_labeled_block() {
+    let out: (i64, i64) = rune! {
+        fn test(cond) {
+            'b: {
+                if cond {
+                    break 'b 1;
+                }
+
+                2
+            }
+        }
+
+        pub fn main() {
+            (test(true), test(false))
+        }
+    };
+    assert_eq!(out, (1, 2));
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 0;
+
+            let value = 'b: {
+                let a = 10;
+
+                loop {
+                    n += 1;
+
+                    if n == 3 {
+                        break 'b a + n;
+                    }
+
+                    if n > 5 {
+                        break;
+                    }
+                }
+
+                0
+            };
+
+            value
+        }
+    };
+    assert_eq!(out, 13);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            'b: {
+                break 'b;
+            };
+
+            42
+        }
+    };
+    assert_eq!(out, 42);
+}
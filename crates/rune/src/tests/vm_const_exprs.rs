@@ -1,5 +1,7 @@
 prelude!();
 
+use ErrorKind::*;
+
 macro_rules! test_op {
     ($ty:ty => $lhs:literal $op:tt $rhs:literal = $result:literal) => {{
         let program = format!(
@@ -264,3 +266,74 @@ fn test_const_block() {
 
     assert_eq!(result, "Hello World");
 }
+
+#[test]
+fn test_assign_to_const() {
+    assert_errors! {
+        r#"const X = 1; const fn f() { X = 2; X } const Y = f(); pub fn main() { Y }"#,
+        span!(28, 29), AssignToConst { name } => assert_eq!(name.as_ref(), "X")
+    };
+
+    assert_errors! {
+        r#"const X = 1; const fn f() { X += 2; X } const Y = f(); pub fn main() { Y }"#,
+        span!(28, 29), AssignToConst { name } => assert_eq!(name.as_ref(), "X")
+    };
+
+    let out: i64 = rune! {
+        const X = 1;
+        const fn f() { let X = 2; X += 1; X }
+        const Y = f();
+        pub fn main() { Y + X }
+    };
+
+    assert_eq!(out, 4);
+}
+
+#[test]
+fn test_max_const_depth() -> Result<()> {
+    fn build(max_const_depth: Option<usize>) -> Result<Diagnostics> {
+        let context = Context::with_default_modules()?;
+        let mut sources =
+            crate::tests::sources(r#"const VALUE = { { { 1 } } }; pub fn main() { VALUE }"#);
+
+        let mut options = rune::Options::default();
+        options.max_const_depth(max_const_depth);
+
+        let mut diagnostics = Diagnostics::new();
+
+        let _ = rune::prepare(&mut sources)
+            .with_context(&context)
+            .with_options(&options)
+            .with_diagnostics(&mut diagnostics)
+            .build();
+
+        Ok(diagnostics)
+    }
+
+    assert!(!build(None)?.has_error());
+    assert!(!build(Some(4))?.has_error());
+
+    let diagnostics = build(Some(3))?;
+    let [diagnostics::Diagnostic::Fatal(error)] = diagnostics.diagnostics() else {
+        panic!(
+            "expected a single error, got {:?}",
+            diagnostics.diagnostics()
+        );
+    };
+
+    let diagnostics::FatalDiagnosticKind::CompileError(error) = error.kind() else {
+        panic!("expected a compile error, got {:?}", error.kind());
+    };
+
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::ScopeDepthExceeded { limit: 3 }
+    ));
+
+    let mut options = rune::Options::default();
+    options.parse_option("max-const-depth=3")?;
+    assert_eq!(options.max_const_depth, Some(3));
+    assert!(options.parse_option("max-const-depth=deep").is_err());
+
+    Ok(())
+}
@@ -68,3 +68,47 @@ fn test_function() {
     let value: Value = function.call(()).unwrap();
     assert!(matches!(value, Value::Integer(3)));
 }
+
+#[test]
+fn test_native_async_vm_result() -> Result<()> {
+    /// Divide two numbers, erroring on division by zero.
+    #[rune::function]
+    async fn checked_div(a: i64, b: i64) -> VmResult<i64> {
+        if b == 0 {
+            return VmResult::err(VmErrorKind::DivideByZero);
+        }
+
+        VmResult::Ok(a / b)
+    }
+
+    let mut m = Module::new();
+    m.function_meta(checked_div)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let value: i64 = run(
+        &context,
+        "pub async fn main() { checked_div(10, 2).await }",
+        ["main"],
+        (),
+    )?;
+    assert_eq!(value, 5);
+
+    let mut sources = crate::tests::sources("pub async fn main() { checked_div(10, 0).await }");
+    let mut diagnostics = Default::default();
+    let result = crate::tests::run_helper::<_, _, i64>(
+        &context,
+        &mut sources,
+        &mut diagnostics,
+        ["main"],
+        (),
+    );
+
+    let Err(crate::tests::TestError::VmError(error)) = result else {
+        panic!("Expected a vm error");
+    };
+
+    assert!(matches!(error.into_kind(), VmErrorKind::DivideByZero));
+    Ok(())
+}
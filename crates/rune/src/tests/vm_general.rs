@@ -168,7 +168,9 @@ fn test_while() {
     };
     assert_eq!(out, 10);
 
-    let out: i64 = rune! {
+    // A `while` loop can't be broken out of with a value.
+    assert_errors! {
+        r#"
         pub fn main() {
             let a = 0;
 
@@ -182,8 +184,14 @@ fn test_while() {
 
             a
         }
+        "#,
+        span!(133, 140), ErrorKind::BreakDoesNotProduceValue
+    };
+
+    assert_errors! {
+        r#"pub fn main() { while true { break 7; } }"#,
+        span!(29, 36), ErrorKind::BreakDoesNotProduceValue
     };
-    assert_eq!(out, 10);
 }
 
 #[test]
@@ -223,6 +231,66 @@ fn test_loop() {
     assert_eq!(out, 10);
 }
 
+#[test]
+fn test_loop_break_value() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let x = loop {
+                break 7;
+            };
+
+            x
+        }
+    };
+    assert_eq!(out, 7);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let x = 'outer: loop {
+                let a = 1;
+
+                loop {
+                    let b = 2;
+                    break 'outer a + b;
+                }
+            };
+
+            x
+        }
+    };
+    assert_eq!(out, 3);
+}
+
+#[test]
+fn test_loop_break_without_value() {
+    let out: ((), i64, ()) = rune! {
+        fn count(limit) {
+            let n = 0;
+
+            loop {
+                n += 1;
+
+                if n == 3 {
+                    break n;
+                }
+
+                if n >= limit {
+                    break;
+                }
+            }
+        }
+
+        pub fn main() {
+            let a = loop {
+                break;
+            };
+
+            (a, count(10), count(2))
+        }
+    };
+    assert_eq!(out, ((), 3, ()));
+}
+
 #[test]
 fn test_for() {
     let out: i64 = rune! {
@@ -282,6 +350,44 @@ fn test_for() {
     assert_eq!(out, true);
 }
 
+#[test]
+fn test_for_break_value() {
+    let out: (i64, bool) = rune! {
+        fn find(items, pred) {
+            for x in items {
+                if pred(x) {
+                    break x;
+                }
+            }
+        }
+
+        pub fn main() {
+            let found = find([1, 2, 3], |x| x > 1);
+            let missing = find([1, 2, 3], |x| x > 3);
+            (found, missing is Tuple)
+        }
+    };
+    assert_eq!(out, (2, true));
+
+    // Labeled breaks can't carry a value out of a `for` loop.
+    assert_errors! {
+        r#"
+        pub fn main() {
+            'outer: for a in 0..10 {
+                for b in 0..10 {
+                    if a * b == 12 {
+                        break 'outer (a, b);
+                    }
+                }
+            }
+        }
+        "#,
+        span!(156, 175), ErrorKind::BreakValueInConditionLoop { label } => {
+            assert_eq!(&*label, "outer");
+        }
+    };
+}
+
 #[test]
 fn test_return() {
     let out: i64 = rune! {